@@ -1,6 +1,5 @@
-use cosyne::{Activation, Config, Cosyne, Environment, ANN};
+use cosyne::{Activation, Config, Cosyne, Environment, StopCondition, ANN};
 use gym_rs::{ActionType, CartPoleEnv, GifRender, GymEnv};
-use std::time::Instant;
 
 fn main() {
     pretty_env_logger::init();
@@ -9,13 +8,15 @@ fn main() {
     let env = Box::new(CartPoleEvaluator {});
     let nn = ANN::new(4, 1, Activation::Tanh);
     let mut cosyne = Cosyne::new(env, nn, config);
-    let t0 = Instant::now();
-    for _ in 0..100 {
-        cosyne.evolve();
-    }
-    let champion = cosyne.champion();
+    let report = cosyne
+        .run(StopCondition::Any(vec![
+            StopCondition::TargetFitness(500.0),
+            StopCondition::MaxGenerations(100),
+        ]))
+        .unwrap();
+    let champion = &report.champion;
     println!("champion: {:?}", champion);
-    println!("training time: {}ms", t0.elapsed().as_millis());
+    println!("training time: {}ms", report.elapsed.as_millis());
     assert!(champion.1 >= 400.0);
 
     render_champion(&mut champion.0.clone());
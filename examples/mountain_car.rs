@@ -1,6 +1,5 @@
-use cosyne::{Activation, Config, Cosyne, Environment, ANN};
+use cosyne::{Activation, Config, Cosyne, Environment, StopCondition, ANN};
 use gym_rs::{ActionType, GifRender, GymEnv, MountainCarEnv};
-use std::time::Instant;
 
 fn main() {
     pretty_env_logger::init();
@@ -10,13 +9,10 @@ fn main() {
     let mut nn = ANN::new(2, 1, Activation::Tanh);
     nn.add_layer(4, Activation::Tanh);
     let mut cosyne = Cosyne::new(env, nn, config);
-    let t0 = Instant::now();
-    for _ in 0..100 {
-        cosyne.evolve();
-    }
-    let champion = cosyne.champion();
+    let report = cosyne.run(StopCondition::MaxGenerations(100)).unwrap();
+    let champion = &report.champion;
     println!("champion: {:?}", champion);
-    println!("training time: {}ms", t0.elapsed().as_millis());
+    println!("training time: {}ms", report.elapsed.as_millis());
 
     let filename = "img/mountain_car_fitness_history.png";
     cosyne.plot_fitness_history(filename, (1920, 1080)).unwrap();
@@ -1,30 +1,25 @@
-use cosyne::{Activation, Config, Cosyne, Environment, PermutationProbF, ANN};
+use cosyne::{Activation, Config, Cosyne, Environment, PermutationProbF, StopCondition, ANN};
 use gym_rs::{ActionType, GifRender, GymEnv, PendulumEnv};
-use std::time::Instant;
 
 fn main() {
     pretty_env_logger::init();
 
-    let config = Config {
-        pop_size: 100,
-        top_ratio_to_recombine: 0.25,
-        mutation_prob: 0.2,
-        mutation_strength: 1.0,
-        perturb_prob: 0.5,
-        permutation_prob_f: PermutationProbF::Relative,
-    };
+    let config = Config::builder()
+        .pop_size(100)
+        .mutation_prob(0.2)
+        .mutation_strength(1.0)
+        .permutation_prob_f(PermutationProbF::Relative)
+        .build()
+        .unwrap();
     let env = Box::new(PendulumEvaluator {});
     let mut nn = ANN::new(3, 1, Activation::Relu);
     nn.add_layer(5, Activation::Relu);
     nn.add_layer(3, Activation::Relu);
     let mut cosyne = Cosyne::new(env, nn, config);
-    let t0 = Instant::now();
-    for _ in 0..1000 {
-        cosyne.evolve();
-    }
-    let champion = cosyne.champion();
+    let report = cosyne.run(StopCondition::MaxGenerations(1000)).unwrap();
+    let champion = &report.champion;
     println!("champion: {:?}", champion);
-    println!("training time: {}ms", t0.elapsed().as_millis());
+    println!("training time: {}ms", report.elapsed.as_millis());
 
     let filename = "img/pendulum_fitness_history.png";
     cosyne.plot_fitness_history(filename, (1920, 1080)).unwrap();
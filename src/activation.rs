@@ -1,5 +1,6 @@
 /// Represents which transfer function to use for evaluating neural networks.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Activation {
     /// Maps input to output directly, as if there is no transfer function.
     Linear,
@@ -0,0 +1,369 @@
+use std::{convert::TryFrom, fs, path::Path};
+
+use failure::{format_err, Error};
+use na::DMatrix as Matrix;
+use serde::{Deserialize, Serialize};
+
+use crate::{Activation, Layer, ANN};
+
+/// Version of the on-disk network format written by this crate.
+/// Increment it whenever AnnFile or LayerFile change and keep reading older versions
+pub const ANN_FORMAT_VERSION: u32 = 2;
+
+/// Leading bytes of the binary encoding, followed by the little endian format version
+const MAGIC: &[u8; 4] = b"CSNE";
+
+/// Stable representation of a network, independent of the in-memory layout of ANN
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnnFile {
+    version: u32,
+    num_inputs: usize,
+    num_outputs: usize,
+    layers: Vec<LayerFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayerFile {
+    input_len: usize,
+    output_len: usize,
+    activation: Activation,
+    // output_len x input_len matrix in row-major order
+    weights: Vec<f64>,
+    biases: Vec<f64>,
+    // output_len x output_len matrix in row-major order, only present for recurrent layers
+    recurrent_weights: Option<Vec<f64>>,
+}
+
+/// Network format version 1, written before recurrent layers were supported
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnnFileV1 {
+    version: u32,
+    num_inputs: usize,
+    num_outputs: usize,
+    layers: Vec<LayerFileV1>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayerFileV1 {
+    input_len: usize,
+    output_len: usize,
+    activation: Activation,
+    weights: Vec<f64>,
+    biases: Vec<f64>,
+}
+
+impl From<AnnFileV1> for AnnFile {
+    fn from(v1: AnnFileV1) -> Self {
+        let layers = v1
+            .layers
+            .into_iter()
+            .map(|l| LayerFile {
+                input_len: l.input_len,
+                output_len: l.output_len,
+                activation: l.activation,
+                weights: l.weights,
+                biases: l.biases,
+                recurrent_weights: None,
+            })
+            .collect();
+
+        AnnFile {
+            version: v1.version,
+            num_inputs: v1.num_inputs,
+            num_outputs: v1.num_outputs,
+            layers,
+        }
+    }
+}
+
+impl AnnFile {
+    fn from_ann(nn: &ANN) -> Self {
+        let layers = nn
+            .layers
+            .iter()
+            .map(|l| LayerFile {
+                input_len: l.input_len,
+                output_len: l.output_len,
+                activation: l.activation,
+                weights: l.weights().transpose().as_slice().into(),
+                biases: l.biases().as_slice().into(),
+                recurrent_weights: l
+                    .recurrent_weights()
+                    .map(|r| r.transpose().as_slice().into()),
+            })
+            .collect();
+
+        AnnFile {
+            version: ANN_FORMAT_VERSION,
+            num_inputs: nn.num_inputs(),
+            num_outputs: nn.num_outputs(),
+            layers,
+        }
+    }
+
+    /// Check the topology for consistency and build the network from it
+    fn into_ann(self) -> Result<ANN, Error> {
+        if self.layers.is_empty() {
+            return Err(format_err!("network has no layers"));
+        }
+        let mut expected_input_len = self.num_inputs;
+        let mut layers: Vec<Layer> = Vec::with_capacity(self.layers.len());
+        for (idx, l) in self.layers.into_iter().enumerate() {
+            if l.input_len != expected_input_len {
+                return Err(format_err!(
+                    "layer {} expects {} inputs, but the previous layer has {} outputs",
+                    idx,
+                    l.input_len,
+                    expected_input_len
+                ));
+            }
+            if l.weights.len() != l.input_len * l.output_len || l.biases.len() != l.output_len {
+                return Err(format_err!(
+                    "layer {} has {} weights and {} biases, expected {} and {}",
+                    idx,
+                    l.weights.len(),
+                    l.biases.len(),
+                    l.input_len * l.output_len,
+                    l.output_len
+                ));
+            }
+            let recurrent_len = l.output_len * l.output_len;
+            if l.recurrent_weights
+                .as_ref()
+                .is_some_and(|r| r.len() != recurrent_len)
+            {
+                return Err(format_err!(
+                    "layer {} has a wrong number of recurrent weights, expected {}",
+                    idx,
+                    recurrent_len
+                ));
+            }
+            let mut layer = match &l.recurrent_weights {
+                Some(r) => {
+                    let mut layer = Layer::new_recurrent(l.input_len, l.output_len, l.activation);
+                    layer.set_recurrent_weights(Matrix::from_row_slice(
+                        l.output_len,
+                        l.output_len,
+                        r,
+                    ));
+                    layer
+                }
+                None => Layer::new(l.input_len, l.output_len, l.activation),
+            };
+            layer.set_weights(Matrix::from_row_slice(
+                l.output_len,
+                l.input_len,
+                &l.weights,
+            ));
+            layer.set_biases(Matrix::from_vec(l.output_len, 1, l.biases));
+            expected_input_len = l.output_len;
+            layers.push(layer);
+        }
+        if expected_input_len != self.num_outputs {
+            return Err(format_err!(
+                "last layer has {} outputs, but the network has {}",
+                expected_input_len,
+                self.num_outputs
+            ));
+        }
+
+        Ok(ANN::from_layers(self.num_inputs, self.num_outputs, layers))
+    }
+}
+
+/// Reject files written by a newer version of the crate
+fn check_version(version: u32) -> Result<(), Error> {
+    if version == 0 || version > ANN_FORMAT_VERSION {
+        return Err(format_err!(
+            "unsupported network format version {}, this crate reads versions 1 to {}",
+            version,
+            ANN_FORMAT_VERSION
+        ));
+    }
+    Ok(())
+}
+
+impl ANN {
+    /// Encode the network topology, weights and biases as JSON
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&AnnFile::from_ann(self))?)
+    }
+
+    /// Decode a network from JSON written by to_json
+    pub fn from_json(json: &str) -> Result<ANN, Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| format_err!("missing network format version"))?;
+        let file: AnnFile = match u32::try_from(version).unwrap_or(u32::MAX) {
+            1 => serde_json::from_value::<AnnFileV1>(value)?.into(),
+            v => {
+                check_version(v)?;
+                serde_json::from_value(value)?
+            }
+        };
+
+        file.into_ann()
+    }
+
+    /// Encode the network topology, weights and biases in a compact binary format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut out: Vec<u8> = MAGIC.to_vec();
+        out.extend_from_slice(&ANN_FORMAT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut out, &AnnFile::from_ann(self))?;
+
+        Ok(out)
+    }
+
+    /// Decode a network from bytes written by to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<ANN, Error> {
+        if bytes.len() < 8 || &bytes[..4] != MAGIC {
+            return Err(format_err!("not a cosyne network file"));
+        }
+        let mut version = [0u8; 4];
+        version.copy_from_slice(&bytes[4..8]);
+        let file: AnnFile = match u32::from_le_bytes(version) {
+            1 => bincode::deserialize::<AnnFileV1>(&bytes[8..])?.into(),
+            v => {
+                check_version(v)?;
+                bincode::deserialize(&bytes[8..])?
+            }
+        };
+
+        file.into_ann()
+    }
+
+    /// Save the network to a file.
+    /// Uses JSON if the file extension is "json" and the binary encoding otherwise
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        if is_json(path) {
+            fs::write(path, self.to_json()?)?;
+        } else {
+            fs::write(path, self.to_bytes()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load a network from a file written by save
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ANN, Error> {
+        let path = path.as_ref();
+        if is_json(path) {
+            ANN::from_json(&fs::read_to_string(path)?)
+        } else {
+            ANN::from_bytes(&fs::read(path)?)
+        }
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network() -> ANN {
+        let mut nn = ANN::new(3, 2, Activation::Tanh);
+        nn.add_layer(4, Activation::Relu);
+        nn
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut nn = network();
+        let json = nn.to_json().unwrap();
+        let mut loaded = ANN::from_json(&json).unwrap();
+
+        assert_eq!(loaded.genes(), nn.genes());
+        assert_eq!(
+            loaded.forward(vec![0.1, -0.2, 0.3]),
+            nn.forward(vec![0.1, -0.2, 0.3])
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut nn = network();
+        let bytes = nn.to_bytes().unwrap();
+        let mut loaded = ANN::from_bytes(&bytes).unwrap();
+
+        assert_eq!(loaded.genes(), nn.genes());
+        assert_eq!(
+            loaded.forward(vec![0.1, -0.2, 0.3]),
+            nn.forward(vec![0.1, -0.2, 0.3])
+        );
+    }
+
+    #[test]
+    fn recurrent_round_trip() {
+        let mut nn = ANN::new(3, 1, Activation::Tanh);
+        nn.add_recurrent_layer(4, Activation::Tanh);
+        let mut loaded = ANN::from_bytes(&nn.to_bytes().unwrap()).unwrap();
+
+        assert!(loaded.layers[0].is_recurrent());
+        assert_eq!(loaded.genes(), nn.genes());
+        for _ in 0..3 {
+            assert_eq!(
+                loaded.forward(vec![0.1, -0.2, 0.3]),
+                nn.forward(vec![0.1, -0.2, 0.3])
+            );
+        }
+    }
+
+    #[test]
+    fn read_version_1() {
+        let json = r#"{
+            "version": 1,
+            "num_inputs": 2,
+            "num_outputs": 1,
+            "layers": [{
+                "input_len": 2,
+                "output_len": 1,
+                "activation": "Linear",
+                "weights": [1.0, 2.0],
+                "biases": [0.5]
+            }]
+        }"#;
+        let mut nn = ANN::from_json(json).unwrap();
+        assert_eq!(nn.forward(vec![1.0, 1.0]), vec![3.5]);
+    }
+
+    #[test]
+    fn weights_are_row_major() {
+        let mut nn = ANN::new(2, 2, Activation::Linear);
+        nn.layers[0].set_weights(Matrix::from_row_slice(2, 2, &[1.0, 2.0, 3.0, 4.0]));
+
+        let file = AnnFile::from_ann(&nn);
+        assert_eq!(file.layers[0].weights, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn reject_unknown_version() {
+        let json = network()
+            .to_json()
+            .unwrap()
+            .replace("\"version\": 2", "\"version\": 999");
+        assert!(ANN::from_json(&json).is_err());
+
+        let mut bytes = network().to_bytes().unwrap();
+        bytes[4] = 0xff;
+        assert!(ANN::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn reject_inconsistent_topology() {
+        let json = network()
+            .to_json()
+            .unwrap()
+            .replace("\"num_outputs\": 2", "\"num_outputs\": 3");
+        assert!(ANN::from_json(&json).is_err());
+    }
+}
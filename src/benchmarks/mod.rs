@@ -0,0 +1,10 @@
+//! Benchmark environments from the CoSyNE paper, implemented in pure Rust,
+//! plus the XOR problem as a quick sanity check.
+//! Useful for reproducing the evaluation counts of the paper
+//! and for catching regressions in the algorithm
+
+mod pole_balancing;
+mod xor;
+
+pub use pole_balancing::{CartPole, DoublePole, DoublePoleNoVelocity};
+pub use xor::Xor;
@@ -0,0 +1,342 @@
+use crate::{Environment, ANN};
+
+// physics constants as used in the CoSyNE paper and the ESP / NEAT pole balancing code
+const GRAVITY: f64 = -9.8;
+const MASS_CART: f64 = 1.0;
+// coefficient of friction of the pole hinges
+const MU_P: f64 = 0.000002;
+const FORCE_MAG: f64 = 10.0;
+// seconds between state updates of the integrator
+const TAU: f64 = 0.01;
+// integration steps per network activation
+const STEPS_PER_ACTION: usize = 2;
+const TRACK_LIMIT: f64 = 2.4;
+const FAILURE_ANGLE: f64 = 36.0 * std::f64::consts::PI / 180.0;
+const INITIAL_ANGLE: f64 = 4.5 * std::f64::consts::PI / 180.0;
+
+/// mass and half length of the long pole
+const LONG_POLE: Pole = Pole {
+    mass: 0.1,
+    half_length: 0.5,
+};
+/// mass and half length of the short pole
+const SHORT_POLE: Pole = Pole {
+    mass: 0.01,
+    half_length: 0.05,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Pole {
+    mass: f64,
+    half_length: f64,
+}
+
+/// Cart with one or more poles hinged on top of it, integrated with 4th order Runge-Kutta.
+/// The state is [x, x_dot, theta_1, theta_1_dot, theta_2, theta_2_dot, ...]
+#[derive(Debug, Clone)]
+struct Simulation {
+    poles: Vec<Pole>,
+    state: Vec<f64>,
+}
+
+impl Simulation {
+    fn new(poles: Vec<Pole>) -> Self {
+        let mut state = vec![0.0; 2 + 2 * poles.len()];
+        // the long pole starts slightly tilted
+        state[2] = INITIAL_ANGLE;
+
+        Self { poles, state }
+    }
+
+    /// Compute the time derivatives of the given state, when applying force to the cart
+    fn derivatives(&self, force: f64, state: &[f64]) -> Vec<f64> {
+        let mut derivs = vec![0.0; state.len()];
+
+        let mut sum_fi: f64 = 0.0;
+        let mut sum_mi: f64 = 0.0;
+        for (p, pole) in self.poles.iter().enumerate() {
+            let theta = state[2 + 2 * p];
+            let theta_dot = state[3 + 2 * p];
+            let ml = pole.half_length * pole.mass;
+            let temp = MU_P * theta_dot / ml;
+            // effective force and mass of the pole acting on the cart
+            sum_fi += ml * theta_dot * theta_dot * theta.sin()
+                + 0.75 * pole.mass * theta.cos() * (temp + GRAVITY * theta.sin());
+            sum_mi += pole.mass * (1.0 - 0.75 * theta.cos() * theta.cos());
+        }
+
+        let x_acc = (force + sum_fi) / (sum_mi + MASS_CART);
+        derivs[0] = state[1];
+        derivs[1] = x_acc;
+        for (p, pole) in self.poles.iter().enumerate() {
+            let theta = state[2 + 2 * p];
+            let theta_dot = state[3 + 2 * p];
+            let temp = MU_P * theta_dot / (pole.half_length * pole.mass);
+            derivs[2 + 2 * p] = theta_dot;
+            derivs[3 + 2 * p] =
+                -0.75 * (x_acc * theta.cos() + GRAVITY * theta.sin() + temp) / pole.half_length;
+        }
+
+        derivs
+    }
+
+    /// Advance the simulation by one network activation, given an action in [-1.0, 1.0]
+    fn step(&mut self, action: f64) {
+        let force = action.clamp(-1.0, 1.0) * FORCE_MAG;
+        for _ in 0..STEPS_PER_ACTION {
+            let y = &self.state;
+            let k1 = self.derivatives(force, y);
+            let k2 = self.derivatives(force, &add_scaled(y, &k1, TAU / 2.0));
+            let k3 = self.derivatives(force, &add_scaled(y, &k2, TAU / 2.0));
+            let k4 = self.derivatives(force, &add_scaled(y, &k3, TAU));
+            self.state = y
+                .iter()
+                .enumerate()
+                .map(|(i, v)| v + TAU / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]))
+                .collect();
+        }
+    }
+
+    /// Return true if the cart left the track or any pole fell over
+    fn failed(&self) -> bool {
+        self.state.iter().any(|v| v.is_nan())
+            || self.state[0].abs() > TRACK_LIMIT
+            || (0..self.poles.len()).any(|p| self.state[2 + 2 * p].abs() > FAILURE_ANGLE)
+    }
+
+    /// Full state scaled to roughly [-1.0, 1.0]
+    fn markovian_inputs(&self) -> Vec<f64> {
+        self.state
+            .iter()
+            .enumerate()
+            .map(|(i, v)| match i {
+                0 => v / 4.8,
+                i if i % 2 == 0 => v / 0.52,
+                _ => v / 2.0,
+            })
+            .collect()
+    }
+
+    /// Cart position and pole angles without any velocities, scaled to roughly [-1.0, 1.0]
+    fn non_markovian_inputs(&self) -> Vec<f64> {
+        let mut inputs = vec![self.state[0] / 4.8];
+        for p in 0..self.poles.len() {
+            inputs.push(self.state[2 + 2 * p] / 0.52);
+        }
+        inputs
+    }
+}
+
+fn add_scaled(y: &[f64], dydx: &[f64], h: f64) -> Vec<f64> {
+    y.iter().zip(dydx).map(|(v, d)| v + h * d).collect()
+}
+
+/// Balance the poles for at most max_steps and return the number of steps until failure
+fn balance<F: Fn(&Simulation) -> Vec<f64>>(
+    poles: Vec<Pole>,
+    nn: &mut ANN,
+    max_steps: usize,
+    inputs: F,
+) -> usize {
+    let mut sim = Simulation::new(poles);
+    nn.reset_state();
+    for step in 0..max_steps {
+        let output = nn.forward(inputs(&sim));
+        sim.step(output[0]);
+        if sim.failed() {
+            return step;
+        }
+    }
+    max_steps
+}
+
+/// Single pole balancing with full state information.
+/// The network gets 4 inputs (cart position and velocity, pole angle and angular velocity)
+/// and its single output in [-1.0, 1.0] is the force applied to the cart,
+/// so an output activation such as Tanh is recommended.
+/// The fitness is the number of steps the pole is balanced
+#[derive(Debug, Clone)]
+pub struct CartPole {
+    max_steps: usize,
+}
+
+impl CartPole {
+    /// Create a new single pole benchmark, balancing for at most 100_000 steps
+    pub fn new() -> Self {
+        Self::with_max_steps(100_000)
+    }
+
+    /// Create a new single pole benchmark, balancing for at most max_steps
+    pub fn with_max_steps(max_steps: usize) -> Self {
+        Self { max_steps }
+    }
+}
+
+impl Default for CartPole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Environment for CartPole {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        balance(vec![LONG_POLE], nn, self.max_steps, |sim| {
+            sim.markovian_inputs()
+        }) as f64
+    }
+}
+
+/// Double pole balancing with full state information (Markovian).
+/// The network gets 6 inputs (cart position and velocity, angle and angular velocity
+/// of both poles) and its single output in [-1.0, 1.0] is the force applied to the cart.
+/// The fitness is the number of steps both poles are balanced
+#[derive(Debug, Clone)]
+pub struct DoublePole {
+    max_steps: usize,
+}
+
+impl DoublePole {
+    /// Create a new double pole benchmark, balancing for at most 100_000 steps
+    pub fn new() -> Self {
+        Self::with_max_steps(100_000)
+    }
+
+    /// Create a new double pole benchmark, balancing for at most max_steps
+    pub fn with_max_steps(max_steps: usize) -> Self {
+        Self { max_steps }
+    }
+}
+
+impl Default for DoublePole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Environment for DoublePole {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        balance(vec![LONG_POLE, SHORT_POLE], nn, self.max_steps, |sim| {
+            sim.markovian_inputs()
+        }) as f64
+    }
+}
+
+/// Double pole balancing without velocity information (non-Markovian),
+/// which requires a recurrent network.
+/// The network gets 3 inputs (cart position and both pole angles)
+/// and its single output in [-1.0, 1.0] is the force applied to the cart.
+/// Uses the damping fitness of Gruau et al. over 1000 steps:
+/// 0.1 * t / 1000 + 0.9 * 0.75 / sum(|x| + |x_dot| + |theta_1| + |theta_1_dot|),
+/// where the sum is taken over the last 100 steps and the second term is 0 if t < 100
+#[derive(Debug, Clone)]
+pub struct DoublePoleNoVelocity {
+    max_steps: usize,
+}
+
+impl DoublePoleNoVelocity {
+    /// Create a new non-Markovian double pole benchmark, balancing for at most 1000 steps
+    pub fn new() -> Self {
+        Self::with_max_steps(1000)
+    }
+
+    /// Create a new non-Markovian double pole benchmark, balancing for at most max_steps
+    pub fn with_max_steps(max_steps: usize) -> Self {
+        Self { max_steps }
+    }
+
+    /// Return the number of steps the network balances both poles, up to max_steps.
+    /// The paper considers the task solved once a network balances for 100_000 steps
+    pub fn steps_balanced(&self, nn: &mut ANN, max_steps: usize) -> usize {
+        balance(vec![LONG_POLE, SHORT_POLE], nn, max_steps, |sim| {
+            sim.non_markovian_inputs()
+        })
+    }
+}
+
+impl Default for DoublePoleNoVelocity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Environment for DoublePoleNoVelocity {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        let mut sim = Simulation::new(vec![LONG_POLE, SHORT_POLE]);
+        nn.reset_state();
+
+        let mut jiggle: Vec<f64> = Vec::with_capacity(self.max_steps);
+        let mut steps: usize = 0;
+        while steps < self.max_steps {
+            let output = nn.forward(sim.non_markovian_inputs());
+            sim.step(output[0]);
+            if sim.failed() {
+                break;
+            }
+            jiggle.push(sim.state[..4].iter().map(|v| v.abs()).sum());
+            steps += 1;
+        }
+
+        let f1 = steps as f64 / 1000.0;
+        let f2 = if steps < 100 {
+            0.0
+        } else {
+            0.75 / jiggle[steps - 100..steps].iter().sum::<f64>()
+        };
+        0.1 * f1 + 0.9 * f2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Activation;
+
+    /// Network that always outputs zero force
+    fn passive(num_inputs: usize) -> ANN {
+        let mut nn = ANN::new(num_inputs, 1, Activation::Tanh);
+        nn.set_genes(&vec![0.0; nn.num_genes()]).unwrap();
+        nn
+    }
+
+    #[test]
+    fn uncontrolled_poles_fall() {
+        let steps = CartPole::new().evaluate(&mut passive(4));
+        assert!(steps > 0.0 && steps < 1000.0);
+
+        let steps = DoublePole::new().evaluate(&mut passive(6));
+        assert!(steps > 0.0 && steps < 1000.0);
+
+        let fitness = DoublePoleNoVelocity::new().evaluate(&mut passive(3));
+        assert!(fitness > 0.0 && fitness < 0.1);
+    }
+
+    #[test]
+    fn tilted_pole_falls_in_its_direction() {
+        let mut sim = Simulation::new(vec![LONG_POLE]);
+        for _ in 0..10 {
+            sim.step(0.0);
+        }
+        assert!(sim.state[2] > INITIAL_ANGLE);
+        assert!(sim.state[3] > 0.0);
+    }
+
+    #[test]
+    fn force_accelerates_cart() {
+        let mut sim = Simulation::new(vec![LONG_POLE, SHORT_POLE]);
+        sim.step(1.0);
+        assert!(sim.state[0] > 0.0);
+        assert!(sim.state[1] > 0.0);
+
+        let mut sim = Simulation::new(vec![LONG_POLE, SHORT_POLE]);
+        sim.step(-1.0);
+        assert!(sim.state[0] < 0.0);
+        assert!(sim.state[1] < 0.0);
+    }
+
+    #[test]
+    fn input_sizes() {
+        let sim = Simulation::new(vec![LONG_POLE, SHORT_POLE]);
+        assert_eq!(sim.markovian_inputs().len(), 6);
+        assert_eq!(sim.non_markovian_inputs().len(), 3);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{Environment, ANN};
+
+/// The XOR problem, which is not linearly separable, so it needs a hidden layer.
+/// The network gets 2 inputs in {0.0, 1.0} and its single output is compared to their XOR.
+/// The fitness is (4 - sum of absolute errors)^2, so a perfect network scores 16.
+/// Networks with an error sum above 4 score 0
+#[derive(Debug, Clone, Default)]
+pub struct Xor {}
+
+impl Xor {
+    /// Create a new XOR benchmark
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Environment for Xor {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        let mut distance: f64 = 0.0;
+        for (a, b) in &[(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (1.0, 1.0)] {
+            let target: f64 = if a != b { 1.0 } else { 0.0 };
+            distance += (target - nn.forward(vec![*a, *b])[0]).abs();
+        }
+
+        (4.0 - distance).max(0.0).powi(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Activation;
+
+    #[test]
+    fn perfect_network() {
+        // hidden neurons compute a OR b and a AND b, the output OR minus AND
+        let mut nn = ANN::new(2, 1, Activation::Relu);
+        nn.add_layer(2, Activation::Linear);
+        nn.set_genes(&[1.0, 1.0, 1.0, 1.0, 0.0, -1.0, 1.0, -2.0, 0.0])
+            .unwrap();
+        assert_eq!(Xor::new().evaluate(&mut nn), 16.0);
+
+        // large errors do not increase the fitness again
+        nn.set_genes(&[10.0; 9]).unwrap();
+        assert_eq!(Xor::new().evaluate(&mut nn), 0.0);
+    }
+}
@@ -0,0 +1,232 @@
+//! Command-line runner for the built-in benchmarks,
+//! e.g. `cosyne cart-pole --config experiment.toml --max-generations 200 --plot`
+
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::{Parser, ValueEnum};
+use cosyne::{
+    benchmarks::{CartPole, DoublePole, DoublePoleNoVelocity, Xor},
+    Activation, Config, ConfigFile, Cosyne, Environment, GenerationStats, StopCondition, ANN,
+};
+use failure::{format_err, Error};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Benchmark {
+    Xor,
+    CartPole,
+    DoublePole,
+    DoublePoleNv,
+}
+
+impl Benchmark {
+    /// Create the environment, balancing for at most max_steps if given
+    fn environment(self, max_steps: Option<usize>) -> Box<dyn Environment> {
+        match (self, max_steps) {
+            (Benchmark::Xor, _) => Box::new(Xor::new()),
+            (Benchmark::CartPole, None) => Box::new(CartPole::new()),
+            (Benchmark::CartPole, Some(n)) => Box::new(CartPole::with_max_steps(n)),
+            (Benchmark::DoublePole, None) => Box::new(DoublePole::new()),
+            (Benchmark::DoublePole, Some(n)) => Box::new(DoublePole::with_max_steps(n)),
+            (Benchmark::DoublePoleNv, None) => Box::new(DoublePoleNoVelocity::new()),
+            (Benchmark::DoublePoleNv, Some(n)) => Box::new(DoublePoleNoVelocity::with_max_steps(n)),
+        }
+    }
+
+    /// Network topology used if the config file does not describe one
+    fn default_network(self) -> ANN {
+        match self {
+            Benchmark::Xor => {
+                let mut nn = ANN::new(2, 1, Activation::Relu);
+                nn.add_layer(3, Activation::Sigmoid);
+                nn
+            }
+            Benchmark::CartPole => ANN::new(4, 1, Activation::Tanh),
+            Benchmark::DoublePole => ANN::new(6, 1, Activation::Tanh),
+            Benchmark::DoublePoleNv => {
+                let mut nn = ANN::new(3, 1, Activation::Tanh);
+                nn.add_recurrent_layer(5, Activation::Tanh);
+                nn
+            }
+        }
+    }
+
+    /// Number of network inputs the environment provides
+    fn num_inputs(self) -> usize {
+        match self {
+            Benchmark::Xor => 2,
+            Benchmark::CartPole => 4,
+            Benchmark::DoublePole => 6,
+            Benchmark::DoublePoleNv => 3,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "cosyne", version, about = "Run CoSyNE on a built-in benchmark")]
+struct Args {
+    /// Benchmark to optimize
+    #[arg(value_enum)]
+    benchmark: Benchmark,
+    /// TOML or JSON config file, optionally describing the network topology
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// Maximum number of steps of the pole balancing benchmarks
+    #[arg(long)]
+    max_steps: Option<usize>,
+    /// Stop after this many generations
+    #[arg(long)]
+    max_generations: Option<usize>,
+    /// Stop after this many network evaluations
+    #[arg(long)]
+    max_evaluations: Option<usize>,
+    /// Stop once the champion reaches this fitness
+    #[arg(long)]
+    target_fitness: Option<f64>,
+    /// Stop after this many seconds
+    #[arg(long)]
+    wall_clock: Option<f64>,
+    /// Stop if the champion did not improve for this many generations
+    #[arg(long)]
+    stagnation: Option<usize>,
+    /// Directory for the champion, the statistics and the plot
+    #[arg(short, long, default_value = "cosyne_out")]
+    output: PathBuf,
+    /// Also plot the fitness history, requires the plot feature
+    #[arg(long)]
+    plot: bool,
+}
+
+impl Args {
+    /// Combine all given stop flags, so the run ends as soon as one of them is met.
+    /// Without any flag the run stops after 100 generations
+    fn stop_condition(&self) -> StopCondition {
+        let mut conditions: Vec<StopCondition> = vec![];
+        if let Some(n) = self.max_generations {
+            conditions.push(StopCondition::MaxGenerations(n));
+        }
+        if let Some(n) = self.max_evaluations {
+            conditions.push(StopCondition::MaxEvaluations(n));
+        }
+        if let Some(f) = self.target_fitness {
+            conditions.push(StopCondition::TargetFitness(f));
+        }
+        if let Some(s) = self.wall_clock {
+            conditions.push(StopCondition::WallClock(Duration::from_secs_f64(s)));
+        }
+        if let Some(n) = self.stagnation {
+            conditions.push(StopCondition::Stagnation(n));
+        }
+        if conditions.is_empty() {
+            conditions.push(StopCondition::MaxGenerations(100));
+        }
+        StopCondition::Any(conditions)
+    }
+}
+
+fn main() {
+    pretty_env_logger::init();
+    let args = Args::parse();
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    if args.plot && !cfg!(feature = "plot") {
+        return Err(format_err!(
+            "plotting requires building with the plot feature"
+        ));
+    }
+
+    let (config, nn) = match &args.config {
+        Some(path) => {
+            let file = ConfigFile::from_file(path)?;
+            let nn = match file.network {
+                Some(spec) => spec.build(),
+                None => args.benchmark.default_network(),
+            };
+            (file.config, nn)
+        }
+        None => (Config::new(100), args.benchmark.default_network()),
+    };
+    if nn.num_inputs() != args.benchmark.num_inputs() || nn.num_outputs() != 1 {
+        return Err(format_err!(
+            "{:?} needs a network with {} inputs and 1 output, got {} inputs and {} outputs",
+            args.benchmark,
+            args.benchmark.num_inputs(),
+            nn.num_inputs(),
+            nn.num_outputs()
+        ));
+    }
+
+    let env = args.benchmark.environment(args.max_steps);
+    let mut cosyne = Cosyne::try_new(env, nn, config)?;
+    let report = cosyne.run(args.stop_condition())?;
+    println!(
+        "stopped after {} generations and {} evaluations in {:.2}s because of {:?}",
+        report.generations,
+        report.evaluations,
+        report.elapsed.as_secs_f64(),
+        report.reasons
+    );
+    println!("champion fitness: {}", report.champion.1);
+
+    fs::create_dir_all(&args.output)?;
+    report.champion.0.save(args.output.join("champion.json"))?;
+    write_stats_csv(cosyne.history(), &args.output.join("stats.csv"))?;
+    if args.plot {
+        plot(&cosyne, &args.output.join("fitness.png"))?;
+    }
+    println!("results written to {}", args.output.display());
+
+    Ok(())
+}
+
+/// Write one line of statistics per generation
+fn write_stats_csv(history: &[GenerationStats], path: &Path) -> Result<(), Error> {
+    let mut w = BufWriter::new(File::create(path)?);
+    writeln!(
+        w,
+        "generation,champion,best,mean,median,worst,std_dev,mean_diversity,evaluations,non_finite,wall_time"
+    )?;
+    for s in history {
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            s.generation,
+            s.champion,
+            s.best,
+            s.mean,
+            s.median,
+            s.worst,
+            s.std_dev,
+            s.mean_diversity(),
+            s.evaluations,
+            s.non_finite,
+            s.wall_time.as_secs_f64()
+        )?;
+    }
+    w.flush()?;
+
+    Ok(())
+}
+
+/// Plot the fitness history, only called if the plot feature is enabled
+#[cfg(feature = "plot")]
+fn plot(cosyne: &Cosyne, path: &Path) -> Result<(), Error> {
+    let filename = path
+        .to_str()
+        .ok_or_else(|| format_err!("invalid plot path {}", path.display()))?;
+    cosyne.plot_fitness_history(filename, (1920, 1080))
+}
+
+#[cfg(not(feature = "plot"))]
+fn plot(_cosyne: &Cosyne, _path: &Path) -> Result<(), Error> {
+    unreachable!("the plot flag is rejected without the plot feature")
+}
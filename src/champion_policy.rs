@@ -0,0 +1,13 @@
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the ways of estimating the fitness of the champion
+pub enum ChampionPolicy {
+    /// Keep the fitness the champion was evaluated with when it became the champion.
+    /// With a noisy environment this is an optimistic outlier
+    #[default]
+    KeepBest,
+    /// Evaluate the champion again every generation and report the running mean
+    /// of all its evaluations. A network only becomes the new champion,
+    /// if its fitness exceeds this estimate
+    Reevaluate,
+}
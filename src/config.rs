@@ -1,7 +1,13 @@
-use crate::permutation_prob_f::PermutationProbF;
+use crate::{
+    ChampionPolicy, CosyneError, Crossover, FitnessAggregation, MutationDistribution,
+    NonFinitePolicy, PermutationProbF, RestartStrategy, Selection,
+};
 
 #[derive(Debug, Clone, Copy)]
-/// Configuration of CoSyNE
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+/// Configuration of CoSyNE.
+/// Create it with Config::new or Config::builder, new options may be added at any time
 pub struct Config {
     /// total number of sub-populations
     pub pop_size: usize,
@@ -13,12 +19,40 @@ pub struct Config {
     pub mutation_strength: f64,
     /// probability of applying a perturbation
     pub perturb_prob: f64,
+    /// distribution of the perturbation added to a mutated weight,
+    /// scaled by mutation_strength
+    pub perturb_distribution: MutationDistribution,
+    /// distribution of the new value of a mutated weight, if it is not perturbed,
+    /// scaled by mutation_strength
+    pub replace_distribution: MutationDistribution,
     /// Permutation function to use
     pub permutation_prob_f: PermutationProbF,
+    /// Crossover operator used for recombining the offspring
+    pub crossover: Crossover,
+    /// Handling of NaN or infinite fitness values returned by the environment
+    pub non_finite_policy: NonFinitePolicy,
+    /// Number of times each network is evaluated per generation,
+    /// values greater than 1 reduce the influence of noisy environments
+    pub evaluations_per_network: usize,
+    /// Combination of the repeated evaluations of a network into its fitness
+    pub fitness_aggregation: FitnessAggregation,
+    /// Estimation of the fitness of the champion
+    pub champion_policy: ChampionPolicy,
+    /// Ranking of networks and weights for recombination and replacement
+    pub selection: Selection,
+    /// Number of nearest neighbours in the novelty archive and the population
+    /// the novelty of a behavior is averaged over
+    pub novelty_neighbors: usize,
+    /// Re-initialization of the population once it stopped making progress,
+    /// None never restarts
+    pub restart: Option<RestartStrategy>,
+    /// Seed of the random number generator.
+    /// The same seed and config yields identical runs, None seeds from system entropy
+    pub seed: Option<u64>,
 }
 
 impl Config {
-    /// Create a new Config
+    /// Create a new Config with default values, see ConfigBuilder for the defaults
     pub fn new(pop_size: usize) -> Self {
         Self {
             pop_size,
@@ -26,7 +60,340 @@ impl Config {
             mutation_prob: 0.3,
             mutation_strength: 0.5,
             perturb_prob: 0.5,
+            perturb_distribution: MutationDistribution::Gaussian(0.4),
+            replace_distribution: MutationDistribution::Uniform(1.0),
             permutation_prob_f: PermutationProbF::Uniform(1.0),
+            crossover: Crossover::SinglePoint,
+            non_finite_policy: NonFinitePolicy::TreatAsWorst,
+            evaluations_per_network: 1,
+            fitness_aggregation: FitnessAggregation::Mean,
+            champion_policy: ChampionPolicy::KeepBest,
+            selection: Selection::Fitness,
+            novelty_neighbors: 15,
+            restart: None,
+            seed: None,
         }
     }
+
+    /// Create a ConfigBuilder, starting from the defaults with a population of 100 networks
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder {
+            config: Config::new(100),
+        }
+    }
+
+    /// Check that the config describes a runnable optimization
+    /// and return the first violation otherwise
+    pub fn validate(&self) -> Result<(), CosyneError> {
+        if !(self.top_ratio_to_recombine > 0.0 && self.top_ratio_to_recombine < 1.0) {
+            return Err(CosyneError::InvalidTopRatio(self.top_ratio_to_recombine));
+        }
+        if (self.pop_size as f64 * self.top_ratio_to_recombine).floor() < 2.0 {
+            return Err(CosyneError::TooFewParents {
+                pop_size: self.pop_size,
+                top_ratio_to_recombine: self.top_ratio_to_recombine,
+            });
+        }
+        check_count("evaluations_per_network", self.evaluations_per_network)?;
+        check_count("novelty_neighbors", self.novelty_neighbors)?;
+        if let Selection::Novelty(weight) = self.selection {
+            check_probability("novelty weight", weight)?;
+        }
+        if let Some(restart) = self.restart {
+            if restart.stagnation.is_none() && restart.min_diversity.is_none() {
+                return Err(CosyneError::NoRestartTrigger);
+            }
+            if let Some(k) = restart.stagnation {
+                check_count("restart stagnation", k)?;
+            }
+            if let Some(min) = restart.min_diversity {
+                check_parameter("restart min_diversity", min)?;
+            }
+        }
+        check_probability("mutation_prob", self.mutation_prob)?;
+        check_strength(self.mutation_strength)?;
+        check_probability("perturb_prob", self.perturb_prob)?;
+        check_distribution("perturb_distribution", self.perturb_distribution)?;
+        check_distribution("replace_distribution", self.replace_distribution)?;
+        if let PermutationProbF::Uniform(p) = self.permutation_prob_f {
+            check_probability("permutation probability", p)?;
+        }
+        match self.crossover {
+            Crossover::MultiPoint(points) => check_count("number of crossover points", points),
+            Crossover::Blend(alpha) => check_parameter("blend crossover alpha", alpha),
+            Crossover::SimulatedBinary(eta) => {
+                check_parameter("simulated binary crossover eta", eta)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Builder for a validated Config, created by Config::builder
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Total number of networks, default 100
+    pub fn pop_size(mut self, pop_size: usize) -> Self {
+        self.config.pop_size = pop_size;
+        self
+    }
+
+    /// Ratio of the best networks that are recombined to create offspring,
+    /// in the range (0, 1), default 0.25
+    pub fn top_ratio_to_recombine(mut self, ratio: f64) -> Self {
+        self.config.top_ratio_to_recombine = ratio;
+        self
+    }
+
+    /// Probability of mutating a weight of the offspring, in the range [0, 1], default 0.3
+    pub fn mutation_prob(mut self, mp: f64) -> Self {
+        self.config.mutation_prob = mp;
+        self
+    }
+
+    /// Scale of the mutations, finite and not negative, default 0.5
+    pub fn mutation_strength(mut self, ms: f64) -> Self {
+        self.config.mutation_strength = ms;
+        self
+    }
+
+    /// Probability of perturbing instead of replacing a mutated weight,
+    /// in the range [0, 1], default 0.5
+    pub fn perturb_prob(mut self, pp: f64) -> Self {
+        self.config.perturb_prob = pp;
+        self
+    }
+
+    /// Distribution of the perturbations, default Gaussian(0.4)
+    pub fn perturb_distribution(mut self, d: MutationDistribution) -> Self {
+        self.config.perturb_distribution = d;
+        self
+    }
+
+    /// Distribution of replaced weights, default Uniform(1.0)
+    pub fn replace_distribution(mut self, d: MutationDistribution) -> Self {
+        self.config.replace_distribution = d;
+        self
+    }
+
+    /// Permutation probability function, default Uniform(1.0)
+    pub fn permutation_prob_f(mut self, f: PermutationProbF) -> Self {
+        self.config.permutation_prob_f = f;
+        self
+    }
+
+    /// Crossover operator, default SinglePoint
+    pub fn crossover(mut self, crossover: Crossover) -> Self {
+        self.config.crossover = crossover;
+        self
+    }
+
+    /// Handling of NaN or infinite fitness values, default TreatAsWorst
+    pub fn non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.config.non_finite_policy = policy;
+        self
+    }
+
+    /// Number of evaluations of each network per generation, at least 1, default 1
+    pub fn evaluations_per_network(mut self, k: usize) -> Self {
+        self.config.evaluations_per_network = k;
+        self
+    }
+
+    /// Combination of repeated evaluations, default Mean
+    pub fn fitness_aggregation(mut self, aggregation: FitnessAggregation) -> Self {
+        self.config.fitness_aggregation = aggregation;
+        self
+    }
+
+    /// Estimation of the champion fitness, default KeepBest
+    pub fn champion_policy(mut self, policy: ChampionPolicy) -> Self {
+        self.config.champion_policy = policy;
+        self
+    }
+
+    /// Ranking for recombination and replacement, default Fitness
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.config.selection = selection;
+        self
+    }
+
+    /// Number of nearest neighbours for the novelty of a behavior, at least 1, default 15
+    pub fn novelty_neighbors(mut self, k: usize) -> Self {
+        self.config.novelty_neighbors = k;
+        self
+    }
+
+    /// Restart the population once it stopped making progress, by default it is never restarted
+    pub fn restart(mut self, strategy: RestartStrategy) -> Self {
+        self.config.restart = Some(strategy);
+        self
+    }
+
+    /// Seed of the random number generator, by default it is seeded from system entropy
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    /// Validate the options and return the Config
+    pub fn build(self) -> Result<Config, CosyneError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Check that a probability is in the range [0, 1]
+pub(crate) fn check_probability(name: &'static str, value: f64) -> Result<(), CosyneError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(CosyneError::ProbabilityOutOfRange { name, value })
+    }
+}
+
+/// Check that a count is at least 1
+pub(crate) fn check_count(name: &'static str, value: usize) -> Result<(), CosyneError> {
+    if value > 0 {
+        Ok(())
+    } else {
+        Err(CosyneError::MustBePositive { name })
+    }
+}
+
+/// Check that the mutation strength is finite and not negative
+pub(crate) fn check_strength(value: f64) -> Result<(), CosyneError> {
+    check_parameter("mutation_strength", value)
+}
+
+/// Check that a parameter is finite and not negative
+pub(crate) fn check_parameter(name: &'static str, value: f64) -> Result<(), CosyneError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(CosyneError::InvalidParameter { name, value })
+    }
+}
+
+/// Check the parameter of a mutation distribution,
+/// the Cauchy distribution additionally requires a positive scale
+fn check_distribution(name: &'static str, d: MutationDistribution) -> Result<(), CosyneError> {
+    match d {
+        MutationDistribution::Cauchy(scale) if scale == 0.0 => {
+            Err(CosyneError::InvalidParameter { name, value: scale })
+        }
+        MutationDistribution::Gaussian(v)
+        | MutationDistribution::Cauchy(v)
+        | MutationDistribution::Laplace(v)
+        | MutationDistribution::Uniform(v) => check_parameter(name, v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_default() {
+        assert_eq!(Config::new(8).validate(), Ok(()));
+    }
+
+    #[test]
+    fn builder() {
+        let config = Config::builder()
+            .pop_size(50)
+            .mutation_prob(0.2)
+            .crossover(Crossover::Uniform)
+            .seed(7)
+            .build()
+            .unwrap();
+        assert_eq!(config.pop_size, 50);
+        assert_eq!(config.mutation_prob, 0.2);
+        assert_eq!(config.crossover, Crossover::Uniform);
+        assert_eq!(config.seed, Some(7));
+        assert_eq!(config.perturb_prob, Config::new(50).perturb_prob);
+
+        assert_eq!(
+            Config::builder().mutation_strength(-1.0).build().err(),
+            Some(CosyneError::InvalidParameter {
+                name: "mutation_strength",
+                value: -1.0
+            })
+        );
+    }
+
+    #[test]
+    fn validate_errors() {
+        let mut config = Config::new(7);
+        assert!(matches!(
+            config.validate(),
+            Err(CosyneError::TooFewParents { pop_size: 7, .. })
+        ));
+
+        config = Config::new(8);
+        config.top_ratio_to_recombine = 1.0;
+        assert_eq!(config.validate(), Err(CosyneError::InvalidTopRatio(1.0)));
+
+        config = Config::new(8);
+        config.perturb_prob = 1.5;
+        assert_eq!(
+            config.validate(),
+            Err(CosyneError::ProbabilityOutOfRange {
+                name: "perturb_prob",
+                value: 1.5
+            })
+        );
+
+        config = Config::new(8);
+        config.mutation_strength = f64::NAN;
+        assert!(matches!(
+            config.validate(),
+            Err(CosyneError::InvalidParameter {
+                name: "mutation_strength",
+                ..
+            })
+        ));
+
+        config = Config::new(8);
+        config.perturb_distribution = MutationDistribution::Cauchy(0.0);
+        assert!(config.validate().is_err());
+
+        config = Config::new(8);
+        config.crossover = Crossover::MultiPoint(0);
+        assert!(config.validate().is_err());
+
+        config = Config::new(8);
+        config.evaluations_per_network = 0;
+        assert_eq!(
+            config.validate(),
+            Err(CosyneError::MustBePositive {
+                name: "evaluations_per_network"
+            })
+        );
+
+        config = Config::new(8);
+        config.selection = Selection::Novelty(1.5);
+        assert!(config.validate().is_err());
+        config.selection = Selection::Novelty(1.0);
+        config.novelty_neighbors = 0;
+        assert!(config.validate().is_err());
+
+        config = Config::new(8);
+        config.restart = Some(RestartStrategy {
+            stagnation: Some(0),
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
+        config.restart = Some(RestartStrategy {
+            min_diversity: Some(-1.0),
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
+        config.restart = Some(RestartStrategy::default());
+        assert_eq!(config.validate(), Err(CosyneError::NoRestartTrigger));
+    }
 }
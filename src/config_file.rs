@@ -0,0 +1,318 @@
+use std::{fs, path::Path};
+
+use failure::{format_err, Error};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Activation, ChampionPolicy, Config, Crossover, FitnessAggregation, MutationDistribution,
+    NonFinitePolicy, PermutationProbF, RestartStrategy, Selection, ANN,
+};
+
+/// Contents of a TOML or JSON config file:
+/// the options of the Config as top level keys and an optional network table.
+/// Options that are not given keep their default value, unknown keys are rejected
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    /// The validated Config
+    pub config: Config,
+    /// The network topology, if the file contains a network table
+    pub network: Option<NetworkSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Description of a fully connected network topology
+pub struct NetworkSpec {
+    /// number of inputs
+    pub inputs: usize,
+    /// number of outputs
+    pub outputs: usize,
+    /// activation function of the output layer
+    pub activation: Activation,
+    /// hidden layers from the inputs to the outputs
+    #[serde(default)]
+    pub hidden: Vec<HiddenLayerSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Description of a single hidden layer
+pub struct HiddenLayerSpec {
+    /// number of neurons
+    pub neurons: usize,
+    /// activation function of the neurons
+    pub activation: Activation,
+    /// whether the layer feeds its previous output back into itself
+    #[serde(default)]
+    pub recurrent: bool,
+}
+
+/// All options are optional, so missing ones keep the defaults of Config::builder
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigSpec {
+    pop_size: Option<usize>,
+    top_ratio_to_recombine: Option<f64>,
+    mutation_prob: Option<f64>,
+    mutation_strength: Option<f64>,
+    perturb_prob: Option<f64>,
+    perturb_distribution: Option<MutationDistribution>,
+    replace_distribution: Option<MutationDistribution>,
+    permutation_prob_f: Option<PermutationProbF>,
+    crossover: Option<Crossover>,
+    non_finite_policy: Option<NonFinitePolicy>,
+    evaluations_per_network: Option<usize>,
+    fitness_aggregation: Option<FitnessAggregation>,
+    champion_policy: Option<ChampionPolicy>,
+    selection: Option<Selection>,
+    novelty_neighbors: Option<usize>,
+    restart: Option<RestartStrategy>,
+    seed: Option<u64>,
+    network: Option<NetworkSpec>,
+}
+
+impl NetworkSpec {
+    /// Create the described network with randomly initialized weights
+    pub fn build(&self) -> ANN {
+        // ANN::add_layer inserts a layer in front of the output layer, which gets the new
+        // activation, so each layer is created with the activation of the one after it
+        let first_activation = self
+            .hidden
+            .first()
+            .map_or(self.activation, |h| h.activation);
+        let mut nn = ANN::new(self.inputs, self.outputs, first_activation);
+        for (k, h) in self.hidden.iter().enumerate() {
+            let next_activation = self
+                .hidden
+                .get(k + 1)
+                .map_or(self.activation, |h| h.activation);
+            if h.recurrent {
+                nn.add_recurrent_layer(h.neurons, next_activation);
+            } else {
+                nn.add_layer(h.neurons, next_activation);
+            }
+        }
+
+        nn
+    }
+}
+
+impl ConfigFile {
+    /// Parse a config file in TOML format
+    pub fn from_toml_str(s: &str) -> Result<ConfigFile, Error> {
+        ConfigFile::from_spec(toml::from_str(s)?)
+    }
+
+    /// Parse a config file in JSON format
+    pub fn from_json_str(s: &str) -> Result<ConfigFile, Error> {
+        ConfigFile::from_spec(serde_json::from_str(s)?)
+    }
+
+    /// Read a config file, which is parsed as JSON if the file extension is ".json"
+    /// and as TOML if it is ".toml"
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<ConfigFile, Error> {
+        let path = path.as_ref();
+        let s = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => ConfigFile::from_toml_str(&s),
+            Some("json") => ConfigFile::from_json_str(&s),
+            _ => Err(format_err!(
+                "unknown config file extension of {}, expected .toml or .json",
+                path.display()
+            )),
+        }
+    }
+
+    /// Route the given options through ConfigBuilder, which validates them
+    fn from_spec(spec: ConfigSpec) -> Result<ConfigFile, Error> {
+        let mut b = Config::builder();
+        if let Some(v) = spec.pop_size {
+            b = b.pop_size(v);
+        }
+        if let Some(v) = spec.top_ratio_to_recombine {
+            b = b.top_ratio_to_recombine(v);
+        }
+        if let Some(v) = spec.mutation_prob {
+            b = b.mutation_prob(v);
+        }
+        if let Some(v) = spec.mutation_strength {
+            b = b.mutation_strength(v);
+        }
+        if let Some(v) = spec.perturb_prob {
+            b = b.perturb_prob(v);
+        }
+        if let Some(v) = spec.perturb_distribution {
+            b = b.perturb_distribution(v);
+        }
+        if let Some(v) = spec.replace_distribution {
+            b = b.replace_distribution(v);
+        }
+        if let Some(v) = spec.permutation_prob_f {
+            b = b.permutation_prob_f(v);
+        }
+        if let Some(v) = spec.crossover {
+            b = b.crossover(v);
+        }
+        if let Some(v) = spec.non_finite_policy {
+            b = b.non_finite_policy(v);
+        }
+        if let Some(v) = spec.evaluations_per_network {
+            b = b.evaluations_per_network(v);
+        }
+        if let Some(v) = spec.fitness_aggregation {
+            b = b.fitness_aggregation(v);
+        }
+        if let Some(v) = spec.champion_policy {
+            b = b.champion_policy(v);
+        }
+        if let Some(v) = spec.selection {
+            b = b.selection(v);
+        }
+        if let Some(v) = spec.novelty_neighbors {
+            b = b.novelty_neighbors(v);
+        }
+        if let Some(v) = spec.restart {
+            b = b.restart(v);
+        }
+        if let Some(v) = spec.seed {
+            b = b.seed(v);
+        }
+        if let Some(network) = &spec.network {
+            if network.inputs == 0 || network.outputs == 0 {
+                return Err(format_err!("a network needs at least one input and output"));
+            }
+            if network.hidden.iter().any(|h| h.neurons == 0) {
+                return Err(format_err!("hidden layers need at least one neuron"));
+            }
+        }
+
+        Ok(ConfigFile {
+            config: b.build()?,
+            network: spec.network,
+        })
+    }
+}
+
+impl Config {
+    /// Parse a Config from TOML, see ConfigFile
+    pub fn from_toml_str(s: &str) -> Result<Config, Error> {
+        Ok(ConfigFile::from_toml_str(s)?.config)
+    }
+
+    /// Parse a Config from JSON, see ConfigFile
+    pub fn from_json_str(s: &str) -> Result<Config, Error> {
+        Ok(ConfigFile::from_json_str(s)?.config)
+    }
+
+    /// Read a Config from a TOML or JSON file, see ConfigFile::from_file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Config, Error> {
+        Ok(ConfigFile::from_file(path)?.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CosyneError;
+
+    const TOML: &str = r#"
+pop_size = 40
+mutation_prob = 0.2
+permutation_prob_f = "Relative"
+crossover = { MultiPoint = 3 }
+perturb_distribution = { Cauchy = 0.1 }
+seed = 7
+
+[restart]
+stagnation = 20
+double_pop_size = true
+
+[network]
+inputs = 4
+outputs = 2
+activation = "Tanh"
+
+[[network.hidden]]
+neurons = 5
+activation = "Relu"
+recurrent = true
+
+[[network.hidden]]
+neurons = 3
+activation = "Sigmoid"
+"#;
+
+    #[test]
+    fn from_toml() {
+        let file = ConfigFile::from_toml_str(TOML).unwrap();
+        let config = file.config;
+        assert_eq!(config.pop_size, 40);
+        assert_eq!(config.mutation_prob, 0.2);
+        assert_eq!(config.permutation_prob_f, PermutationProbF::Relative);
+        assert_eq!(config.crossover, Crossover::MultiPoint(3));
+        assert_eq!(
+            config.perturb_distribution,
+            MutationDistribution::Cauchy(0.1)
+        );
+        assert_eq!(config.seed, Some(7));
+        assert_eq!(
+            config.restart,
+            Some(RestartStrategy {
+                stagnation: Some(20),
+                double_pop_size: true,
+                ..Default::default()
+            })
+        );
+        // missing options keep their defaults
+        assert_eq!(config.perturb_prob, Config::new(40).perturb_prob);
+
+        let nn = file.network.unwrap().build();
+        assert_eq!(nn.num_inputs(), 4);
+        assert_eq!(nn.num_outputs(), 2);
+        let layers: Vec<(usize, usize, Activation, bool)> = nn
+            .layers
+            .iter()
+            .map(|l| (l.input_len, l.output_len, l.activation, l.is_recurrent()))
+            .collect();
+        assert_eq!(
+            layers,
+            vec![
+                (4, 5, Activation::Relu, true),
+                (5, 3, Activation::Sigmoid, false),
+                (3, 2, Activation::Tanh, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_json() {
+        let config = Config::from_json_str(
+            r#"{"pop_size": 20, "crossover": "Uniform", "non_finite_policy": {"Reevaluate": 2}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.pop_size, 20);
+        assert_eq!(config.crossover, Crossover::Uniform);
+        assert_eq!(config.non_finite_policy, NonFinitePolicy::Reevaluate(2));
+    }
+
+    #[test]
+    fn unknown_key() {
+        let err = Config::from_toml_str("mutation_rate = 0.2").unwrap_err();
+        assert!(err.to_string().contains("unknown field `mutation_rate`"));
+
+        let err = Config::from_toml_str("[restart]\nstagnation = 5\npatience = 3").unwrap_err();
+        assert!(err.to_string().contains("unknown field `patience`"));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let err = Config::from_toml_str("perturb_prob = 2.0").unwrap_err();
+        assert_eq!(
+            err.downcast::<CosyneError>().unwrap(),
+            CosyneError::ProbabilityOutOfRange {
+                name: "perturb_prob",
+                value: 2.0
+            }
+        );
+    }
+}
@@ -1,72 +1,387 @@
-use crate::{Config, Environment, Population, ANN};
+use std::time::{Duration, Instant};
 
-use crate::population::DEFAULT_FIT;
+use crate::{
+    config::{check_probability, check_strength},
+    novelty, pareto, BehaviorEnvironment, ChampionPolicy, Config, CosyneError, CosyneObserver,
+    Elite, Environment, FitnessAggregation, GenerationStats, MapElites, MultiObjectiveEnvironment,
+    NonFinitePolicy, Population, RestartStrategy, RunReport, Selection, StopCondition, StopReason,
+    ANN,
+};
+
+use crate::{population::DEFAULT_FIT, stop_condition::Progress};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 #[cfg(feature = "plot")]
 use {crate::plot_values, failure::Error};
+#[cfg(feature = "serde")]
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        fs::File,
+        io::{BufReader, BufWriter},
+        path::Path,
+    },
+};
 
 /// The main optimization struct
 pub struct Cosyne {
     config: Config,
-    env: Box<dyn Environment>,
+    env: Evaluator,
     pop: Population,
     generation: usize,
     champion_fit_history: Vec<f64>,
     champion: (ANN, f64), // network with fitness
+    // non-dominated networks found so far with their objectives
+    pareto_front: Vec<(ANN, Vec<f64>)>,
+    // behaviors of the most novel network of each generation with Selection::Novelty
+    novelty_archive: Vec<Vec<f64>>,
+    // first generation of every re-initialized population
+    restart_generations: Vec<usize>,
+    // networks of the last evaluated generation with their fitness, fittest first
+    last_generation: Vec<(ANN, f64)>,
+    // quality-diversity archive every evaluated network is inserted into
+    map_elites: Option<MapElites>,
+    // number of generations the champion fitness is averaged over
+    champion_samples: usize,
+    history: Vec<GenerationStats>,
+    evaluations: usize,
+    start: Instant,
+    observers: Vec<Box<dyn CosyneObserver>>,
 }
 
 impl Cosyne {
     /// Set the mutation probability of the population
-    /// panics in debug mode if mp < 0.0 || mp > 1.0
-    pub fn set_mutation_prob(&mut self, mp: f64) {
-        debug_assert!((0.0..=1.0).contains(&mp));
+    /// returns an error if mp < 0.0 || mp > 1.0
+    pub fn set_mutation_prob(&mut self, mp: f64) -> Result<(), CosyneError> {
+        check_probability("mutation_prob", mp)?;
+        self.config.mutation_prob = mp;
         self.pop.set_mutation_prob(mp);
+        Ok(())
     }
 
     /// Set the mutation strength of the population
-    /// panics in debug mode if mp < 0.0 || mp > 1.0
-    pub fn set_mutation_strength(&mut self, ms: f64) {
-        debug_assert!((0.0..=1.0).contains(&ms));
+    /// returns an error if ms < 0.0 or ms is not finite
+    pub fn set_mutation_strength(&mut self, ms: f64) -> Result<(), CosyneError> {
+        check_strength(ms)?;
+        self.config.mutation_strength = ms;
         self.pop.set_mutation_strength(ms);
+        Ok(())
     }
 
     /// Set the perturb probability of the population
-    /// panics in debug mode if pp < 0.0 || pp > 1.0
-    pub fn set_perturb_prob(&mut self, pp: f64) {
-        debug_assert!((0.0..=1.0).contains(&pp));
+    /// returns an error if pp < 0.0 || pp > 1.0
+    pub fn set_perturb_prob(&mut self, pp: f64) -> Result<(), CosyneError> {
+        check_probability("perturb_prob", pp)?;
+        self.config.perturb_prob = pp;
         self.pop.set_perturb_prob(pp);
+        Ok(())
     }
 
     /// Create a new CoSyNE optimizer with a given environment, neural network and config
+    /// panics if the config or the initializers of the network are invalid,
+    /// use try_new to handle the error instead
     pub fn new(env: Box<dyn Environment>, nn: ANN, config: Config) -> Self {
+        match Cosyne::try_new(env, nn, config) {
+            Ok(cosyne) => cosyne,
+            Err(e) => panic!("invalid config: {}", e),
+        }
+    }
+
+    /// Create a new CoSyNE optimizer with a given environment, neural network and config
+    /// returns an error if the config or the initializers of the network are invalid
+    pub fn try_new(
+        env: Box<dyn Environment>,
+        nn: ANN,
+        config: Config,
+    ) -> Result<Self, CosyneError> {
+        Cosyne::from_evaluator(Evaluator::Single(env), nn, config)
+    }
+
+    /// Create a new CoSyNE optimizer for an environment with several objectives.
+    /// The fitness of a network is the sum of its objectives,
+    /// use Selection::Pareto to rank the networks by Pareto dominance instead.
+    /// panics if the config or the initializers of the network are invalid,
+    /// use try_new_multi_objective to handle the error instead
+    pub fn new_multi_objective(
+        env: Box<dyn MultiObjectiveEnvironment>,
+        nn: ANN,
+        config: Config,
+    ) -> Self {
+        match Cosyne::try_new_multi_objective(env, nn, config) {
+            Ok(cosyne) => cosyne,
+            Err(e) => panic!("invalid config: {}", e),
+        }
+    }
+
+    /// Create a new CoSyNE optimizer for an environment with several objectives
+    /// returns an error if the config or the initializers of the network are invalid
+    pub fn try_new_multi_objective(
+        env: Box<dyn MultiObjectiveEnvironment>,
+        nn: ANN,
+        config: Config,
+    ) -> Result<Self, CosyneError> {
+        Cosyne::from_evaluator(Evaluator::MultiObjective(env), nn, config)
+    }
+
+    /// Create a new CoSyNE optimizer for an environment describing the behavior of networks,
+    /// which is required for Selection::Novelty.
+    /// panics if the config or the initializers of the network are invalid,
+    /// use try_new_with_behavior to handle the error instead
+    pub fn new_with_behavior(env: Box<dyn BehaviorEnvironment>, nn: ANN, config: Config) -> Self {
+        match Cosyne::try_new_with_behavior(env, nn, config) {
+            Ok(cosyne) => cosyne,
+            Err(e) => panic!("invalid config: {}", e),
+        }
+    }
+
+    /// Create a new CoSyNE optimizer for an environment describing the behavior of networks
+    /// returns an error if the config or the initializers of the network are invalid
+    pub fn try_new_with_behavior(
+        env: Box<dyn BehaviorEnvironment>,
+        nn: ANN,
+        config: Config,
+    ) -> Result<Self, CosyneError> {
+        Cosyne::from_evaluator(Evaluator::Behavior(env), nn, config)
+    }
+
+    /// Create a new CoSyNE optimizer evaluating networks with the given evaluator
+    fn from_evaluator(env: Evaluator, nn: ANN, config: Config) -> Result<Self, CosyneError> {
+        config.validate()?;
+        env.check_selection(config.selection)?;
+        nn.validate_initializers()?;
         let pop = Population::new(config, &nn);
         let champion = (pop.get_network(0), DEFAULT_FIT);
-        Self {
+        Ok(Self {
             config,
             env,
             pop,
             generation: 0,
             champion_fit_history: vec![],
             champion,
+            pareto_front: vec![],
+            novelty_archive: vec![],
+            restart_generations: vec![],
+            last_generation: vec![],
+            map_elites: None,
+            champion_samples: 0,
+            history: vec![],
+            evaluations: 0,
+            start: Instant::now(),
+            observers: vec![],
+        })
+    }
+
+    /// Register an observer, which is notified about the progress of the optimization
+    pub fn add_observer(&mut self, observer: Box<dyn CosyneObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Attach a MAP-Elites archive, which every network evaluated from now on is inserted into.
+    /// returns an error if the environment does not describe behaviors
+    pub fn set_map_elites(&mut self, archive: MapElites) -> Result<(), CosyneError> {
+        if !self.env.describes_behavior() {
+            return Err(CosyneError::MissingBehavior);
+        }
+        self.map_elites = Some(archive);
+        Ok(())
+    }
+
+    /// Replace networks of the population with the fittest elites of the MAP-Elites archive,
+    /// at most pop_size of them. The weights of the elites have no fitness yet.
+    /// Returns the number of replaced networks, which is 0 without an archive,
+    /// or an error if the elites have a different topology than the population
+    pub fn seed_from_map_elites(&mut self) -> Result<usize, CosyneError> {
+        let archive = match &self.map_elites {
+            Some(archive) => archive,
+            None => return Ok(0),
+        };
+        let mut elites: Vec<&Elite> = archive.elites().collect();
+        elites.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+        elites.truncate(self.config.pop_size);
+        for (j, elite) in elites.iter().enumerate() {
+            self.pop.set_network(j, &elite.network)?;
+        }
+        Ok(elites.len())
+    }
+
+    /// Return clones of the n fittest networks of the last evaluated generation
+    pub(crate) fn emigrants(&self, n: usize) -> Vec<ANN> {
+        self.last_generation
+            .iter()
+            .take(n)
+            .map(|(nn, _)| nn.clone())
+            .collect()
+    }
+
+    /// Replace the least fit networks of the evaluated generation by the immigrants,
+    /// between record_generation and breed, so no unevaluated offspring are lost.
+    /// The fitness of other islands may not be comparable, so the weights of the immigrants
+    /// get the mean weight fitness of the fittest network, which makes them parents
+    /// of the offspring and keeps them in the population.
+    /// returns an error if an immigrant has a different topology than the population
+    pub(crate) fn immigrate(&mut self, immigrants: &[ANN]) -> Result<(), CosyneError> {
+        let ranking = self.pop.ranking();
+        let fitness = ranking
+            .last()
+            .map_or(DEFAULT_FIT, |j| self.pop.mean_weight_fitness(*j));
+        for (j, nn) in ranking.into_iter().zip(immigrants) {
+            self.pop.set_network_with_fitness(j, nn, fitness)?;
         }
+        Ok(())
+    }
+
+    /// Perform an evolutionary step.
+    /// Returns an error if a fitness is not finite and the NonFinitePolicy is Abort
+    /// or a behavior does not match the dimensions of the MapElites archive,
+    /// in which case the population is left unchanged
+    pub fn evolve(&mut self) -> Result<(), CosyneError> {
+        let evaluated = self.evaluate_checked()?;
+        self.record_generation(evaluated);
+        self.breed();
+        Ok(())
     }
 
-    /// Perform an evolutionary step
-    pub fn evolve(&mut self) {
+    /// Evaluate the population and check the results against the config,
+    /// without changing the population, the champion or any statistics
+    pub(crate) fn evaluate_checked(&mut self) -> Result<Vec<Evaluation>, CosyneError> {
+        for o in &mut self.observers {
+            o.on_generation_start(self.generation);
+        }
+
         // evaluate entire population
+        let evaluated = self.evaluate_population();
+
+        // handle the non-finite fitnesses left after re-evaluating
+        if let Some((network, e)) = evaluated
+            .iter()
+            .enumerate()
+            .find(|(_, e)| !e.fitness.is_finite())
+        {
+            if self.config.non_finite_policy == NonFinitePolicy::Abort {
+                return Err(CosyneError::NonFiniteFitness {
+                    network,
+                    fitness: e.fitness,
+                });
+            }
+        }
+        if let Some(archive) = &self.map_elites {
+            for e in &evaluated {
+                archive.check_behavior(&e.behavior)?;
+            }
+        }
+
+        Ok(evaluated)
+    }
+
+    /// Assign the fitness of a checked evaluation to the population
+    /// and update the champion, the archives and the history
+    pub(crate) fn record_generation(&mut self, evaluated: Vec<Evaluation>) {
+        let mut non_finite: usize = evaluated.iter().map(|e| e.non_finite).sum();
+        self.evaluations += evaluated.iter().map(|e| e.evaluations).sum::<usize>();
+        // worst finite value of every objective,
+        // so networks without any finite value can never become the champion
+        let num_objectives = evaluated.first().map_or(0, |e| e.objectives.len());
+        let worst: Vec<f64> = (0..num_objectives)
+            .map(|o| {
+                evaluated
+                    .iter()
+                    .map(|e| e.objectives[o])
+                    .filter(|v| v.is_finite())
+                    .reduce(f64::min)
+                    .unwrap_or(DEFAULT_FIT)
+            })
+            .collect();
+
+        // re-test the champion, so its fitness is a running estimate
+        // instead of its luckiest evaluation
+        if self.config.champion_policy == ChampionPolicy::Reevaluate && self.champion_samples > 0 {
+            let e = evaluate_network(&self.env, &self.champion.0, &self.config);
+            self.evaluations += e.evaluations;
+            non_finite += e.non_finite;
+            if e.fitness.is_finite() {
+                let n = self.champion_samples as f64;
+                self.champion.1 = (self.champion.1 * n + e.fitness) / (n + 1.0);
+                self.champion_samples += 1;
+            }
+        }
+        if non_finite > 0 {
+            warn!(
+                "gen {}, {} evaluations returned a non-finite fitness",
+                self.generation, non_finite
+            );
+        }
+
+        // results are in index order, so the champion and the observer notifications
+        // are deterministic
         let mut fits: Vec<f64> = Vec::with_capacity(self.config.pop_size);
-        for j in 0..self.config.pop_size {
-            // get the genes of network chromosome
-            let mut net = self.pop.get_network(j);
-            // evaluate the fitness in environment
-            let fit = self.env.evaluate(&mut net);
+        let mut objectives: Vec<Vec<f64>> = Vec::with_capacity(self.config.pop_size);
+        let mut nets: Vec<ANN> = Vec::with_capacity(self.config.pop_size);
+        let mut behaviors: Vec<Vec<f64>> = Vec::with_capacity(self.config.pop_size);
+        for (j, e) in evaluated.into_iter().enumerate() {
+            if let Some(archive) = &mut self.map_elites {
+                archive
+                    .insert(&e.net, e.fitness, &e.behavior)
+                    .expect("behaviors were checked by evaluate_checked");
+            }
+            let objs: Vec<f64> = e
+                .objectives
+                .iter()
+                .zip(&worst)
+                .map(|(v, w)| if v.is_finite() { *v } else { *w })
+                .collect();
+            // several objectives at DEFAULT_FIT overflow to -inf
+            let fit: f64 = objs.iter().sum::<f64>().max(DEFAULT_FIT);
             fits.push(fit);
+            objectives.push(objs);
+            for o in &mut self.observers {
+                o.on_evaluated(j, fit);
+            }
             if fit > self.champion.1 {
                 // save the champion with fitness
-                self.champion = (net.clone(), fit);
+                self.champion = (e.net.clone(), fit);
+                self.champion_samples = 1;
+                for o in &mut self.observers {
+                    o.on_new_champion(&self.champion.0, fit);
+                }
             }
+            nets.push(e.net);
+            behaviors.push(e.behavior);
+        }
+        match self.config.selection {
+            Selection::Fitness => self.pop.update_fitnesses(&fits),
+            Selection::Pareto => self.pop.update_objectives(&objectives),
+            Selection::Novelty(weight) => {
+                // the blend is normalized within this generation, so it is not averaged
+                let scores = self.novelty_scores(behaviors, &fits, weight);
+                self.pop.set_scores(&scores);
+            }
+        }
+        self.update_pareto_front(&nets, objectives);
+        // several networks without any finite fitness would overflow the mean to -inf
+        let mut scored: Vec<f64> = fits.iter().copied().filter(|f| *f != DEFAULT_FIT).collect();
+        if scored.is_empty() && !fits.is_empty() {
+            scored.push(DEFAULT_FIT);
         }
-        self.pop.update_fitnesses(&fits);
+        let stats = GenerationStats::new(
+            self.generation,
+            self.champion.1,
+            &scored,
+            self.pop.diversity(),
+            self.evaluations,
+            non_finite,
+            self.start.elapsed(),
+        );
+        for o in &mut self.observers {
+            o.on_generation_end(&stats);
+        }
+        self.history.push(stats);
+        self.last_generation = nets.into_iter().zip(fits).collect();
+        self.last_generation.sort_by(|a, b| b.1.total_cmp(&a.1));
+    }
 
+    /// Breed the next generation from the evaluated one and restart if necessary,
+    /// the second half of evolve
+    pub(crate) fn breed(&mut self) {
         self.pop.evolve();
         self.champion_fit_history.push(self.champion().1);
 
@@ -76,6 +391,135 @@ impl Cosyne {
             self.champion().1
         );
         self.generation += 1;
+
+        if let Some(strategy) = self.config.restart {
+            let first = self.restart_generations.last().map_or(0, |g| *g);
+            if strategy.should_restart(&self.history[first..]) {
+                self.restart(strategy);
+            }
+        }
+    }
+
+    /// Replace the population with a new random one, keeping the champion and the history
+    fn restart(&mut self, strategy: RestartStrategy) {
+        if strategy.double_pop_size {
+            self.config.pop_size *= 2;
+        }
+        // the new population must not repeat the initial one of a seeded run
+        let mut config = self.config;
+        config.seed = config
+            .seed
+            .map(|seed| seed.wrapping_add(self.restart_generations.len() as u64 + 1));
+        self.pop = Population::new(config, &self.champion.0);
+        if strategy.keep_champion {
+            self.pop
+                .set_network(0, &self.champion.0)
+                .expect("the champion has the topology of the population");
+        }
+        self.restart_generations.push(self.generation);
+        info!(
+            "gen {}, restart {} with pop_size {}",
+            self.generation,
+            self.restart_generations.len(),
+            self.config.pop_size
+        );
+    }
+
+    /// Evolve until the stop condition is met or an observer requests to stop
+    /// and report why the run stopped.
+    /// Returns an error if the stop condition contains an empty Any or All or Stagnation(0),
+    /// or a generation could not be evolved, see evolve
+    pub fn run(&mut self, stop: StopCondition) -> Result<RunReport, CosyneError> {
+        stop.validate()?;
+        let t0 = Instant::now();
+        let first_generation = self.generation;
+        loop {
+            let mut reasons = stop.check(&self.progress()).unwrap_or_default();
+            if self.observers.iter().any(|o| o.should_stop()) {
+                reasons.push(StopReason::Observer);
+            }
+            if !reasons.is_empty() {
+                return Ok(RunReport {
+                    reasons,
+                    generations: self.generation - first_generation,
+                    evaluations: self.evaluations,
+                    elapsed: t0.elapsed(),
+                    champion: self.champion.clone(),
+                });
+            }
+            self.evolve()?;
+        }
+    }
+
+    /// Merge the networks of this generation into the Pareto front,
+    /// which keeps at most pop_size of the least crowded non-dominated networks
+    fn update_pareto_front(&mut self, nets: &[ANN], objectives: Vec<Vec<f64>>) {
+        let previous: Vec<(ANN, Vec<f64>)> = std::mem::take(&mut self.pareto_front);
+        let num_previous = previous.len();
+        let mut candidates: Vec<Vec<f64>> = previous.iter().map(|(_, o)| o.clone()).collect();
+        candidates.extend(objectives);
+        let mut front = pareto::pareto_front(&candidates, self.config.pop_size);
+        front.sort_unstable();
+        // only the networks of this generation that made it into the front are cloned
+        let new: Vec<(ANN, Vec<f64>)> = front
+            .iter()
+            .filter(|i| **i >= num_previous)
+            .map(|i| (nets[*i - num_previous].clone(), candidates[*i].clone()))
+            .collect();
+        self.pareto_front = previous
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| front.binary_search(i).is_ok())
+            .map(|(_, c)| c)
+            .chain(new)
+            .collect();
+    }
+
+    /// Rate the behaviors of this generation by their novelty, archive the most novel one
+    /// and blend the novelty with the fitness
+    fn novelty_scores(&mut self, behaviors: Vec<Vec<f64>>, fits: &[f64], weight: f64) -> Vec<f64> {
+        let novelty = novelty::novelty(
+            &behaviors,
+            &self.novelty_archive,
+            self.config.novelty_neighbors,
+        );
+        if let Some(i) = novelty::most_novel(&novelty) {
+            if novelty[i] > 0.0 {
+                self.novelty_archive.push(behaviors[i].clone());
+            }
+        }
+        novelty::blend(fits, &novelty, weight)
+    }
+
+    /// Summarize the state of the run for checking stop conditions
+    fn progress(&self) -> Progress<'_> {
+        Progress {
+            generation: self.generation,
+            evaluations: self.evaluations,
+            champion_fitness: self.champion.1,
+            champion_fit_history: &self.champion_fit_history,
+            elapsed: self.elapsed(),
+        }
+    }
+
+    /// Evaluate every network of the population one after another
+    #[cfg(not(feature = "parallel"))]
+    fn evaluate_population(&self) -> Vec<Evaluation> {
+        (0..self.config.pop_size)
+            .map(|j| evaluate_network(&self.env, &self.pop.get_network(j), &self.config))
+            .collect()
+    }
+
+    /// Evaluate all networks of the population concurrently on the rayon thread pool
+    #[cfg(feature = "parallel")]
+    fn evaluate_population(&self) -> Vec<Evaluation> {
+        let env = &self.env;
+        let pop = &self.pop;
+        let config = &self.config;
+        (0..self.config.pop_size)
+            .into_par_iter()
+            .map(|j| evaluate_network(env, &pop.get_network(j), config))
+            .collect()
     }
 
     /// Get the current champion and its fitness
@@ -83,15 +527,286 @@ impl Cosyne {
         &self.champion
     }
 
+    /// Get the non-dominated networks found so far with their objectives.
+    /// For a single objective environment this is the champion.
+    /// Non-finite objectives are replaced by the worst finite value of their generation
+    pub fn pareto_front(&self) -> &[(ANN, Vec<f64>)] {
+        &self.pareto_front
+    }
+
+    /// Get the archived behaviors, which are the most novel behavior of each generation
+    /// with Selection::Novelty, and empty otherwise
+    pub fn novelty_archive(&self) -> &[Vec<f64>] {
+        &self.novelty_archive
+    }
+
+    /// Get the first generation of every population re-initialized by the RestartStrategy
+    pub fn restart_generations(&self) -> &[usize] {
+        &self.restart_generations
+    }
+
+    /// Get the MAP-Elites archive, if one is attached
+    pub fn map_elites(&self) -> Option<&MapElites> {
+        self.map_elites.as_ref()
+    }
+
+    /// Get the champion fitness after each generation
+    pub fn champion_fit_history(&self) -> &[f64] {
+        &self.champion_fit_history
+    }
+
+    /// Get the number of generations evolved so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Get the total number of network evaluations so far
+    pub fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+
+    /// Get the wall time elapsed since the start of the optimization
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Get the statistics of every generation so far
+    pub fn history(&self) -> &[GenerationStats] {
+        &self.history
+    }
+
+    #[cfg(feature = "serde")]
+    /// Write the complete optimizer state to a file, so the run can be resumed later
+    /// with load_checkpoint. The environment is not part of the checkpoint
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<(), failure::Error> {
+        let checkpoint = Checkpoint {
+            config: self.config,
+            pop: self.pop.clone(),
+            generation: self.generation,
+            champion_fit_history: self.champion_fit_history.clone(),
+            champion: self.champion.clone(),
+            pareto_front: self.pareto_front.clone(),
+            novelty_archive: self.novelty_archive.clone(),
+            map_elites: self.map_elites.clone(),
+            restart_generations: self.restart_generations.clone(),
+            champion_samples: self.champion_samples,
+            history: self.history.clone(),
+            evaluations: self.evaluations,
+        };
+        let writer = BufWriter::new(File::create(path)?);
+        bincode::serialize_into(writer, &checkpoint)?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Resume a run from a checkpoint file written by save_checkpoint,
+    /// continuing to evaluate networks in the given environment
+    pub fn load_checkpoint<P: AsRef<Path>>(
+        path: P,
+        env: Box<dyn Environment>,
+    ) -> Result<Self, failure::Error> {
+        Cosyne::from_checkpoint(path, Evaluator::Single(env))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Resume a run from a checkpoint file written by save_checkpoint,
+    /// continuing to evaluate networks in the given multi-objective environment
+    pub fn load_multi_objective_checkpoint<P: AsRef<Path>>(
+        path: P,
+        env: Box<dyn MultiObjectiveEnvironment>,
+    ) -> Result<Self, failure::Error> {
+        Cosyne::from_checkpoint(path, Evaluator::MultiObjective(env))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Resume a run from a checkpoint file written by save_checkpoint,
+    /// continuing to evaluate networks in the given environment describing behaviors
+    pub fn load_behavior_checkpoint<P: AsRef<Path>>(
+        path: P,
+        env: Box<dyn BehaviorEnvironment>,
+    ) -> Result<Self, failure::Error> {
+        Cosyne::from_checkpoint(path, Evaluator::Behavior(env))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Resume a run from a checkpoint file, evaluating networks with the given evaluator
+    fn from_checkpoint<P: AsRef<Path>>(path: P, env: Evaluator) -> Result<Self, failure::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let checkpoint: Checkpoint = bincode::deserialize_from(reader)?;
+        env.check_selection(checkpoint.config.selection)?;
+        if checkpoint.map_elites.is_some() && !env.describes_behavior() {
+            return Err(CosyneError::MissingBehavior.into());
+        }
+        // restarts draw a new population from the initializers of the champion
+        checkpoint.champion.0.validate_initializers()?;
+        // continue measuring wall time where the checkpointed run stopped
+        let elapsed = checkpoint
+            .history
+            .last()
+            .map_or(Duration::ZERO, |s| s.wall_time);
+        let now = Instant::now();
+        let start = now.checked_sub(elapsed).unwrap_or(now);
+
+        Ok(Self {
+            config: checkpoint.config,
+            env,
+            pop: checkpoint.pop,
+            generation: checkpoint.generation,
+            champion_fit_history: checkpoint.champion_fit_history,
+            champion: checkpoint.champion,
+            pareto_front: checkpoint.pareto_front,
+            novelty_archive: checkpoint.novelty_archive,
+            restart_generations: checkpoint.restart_generations,
+            last_generation: vec![],
+            map_elites: checkpoint.map_elites,
+            champion_samples: checkpoint.champion_samples,
+            history: checkpoint.history,
+            evaluations: checkpoint.evaluations,
+            start,
+            observers: vec![],
+        })
+    }
+
     #[cfg(feature = "plot")]
-    /// Plots the historical fitness values of the population
+    /// Plots the historical fitness values of the population,
+    /// which are the champion, best, mean, median and worst fitness of each generation
     pub fn plot_fitness_history(
         &self,
         filename: &str,
         resolution: (u32, u32),
     ) -> Result<(), Error> {
-        // TODO: plot worst and average fitness as well
+        let series =
+            |f: fn(&GenerationStats) -> f64| -> Vec<f64> { self.history.iter().map(f).collect() };
+        plot_values(
+            &[
+                ("champion", series(|s| s.champion)),
+                ("best", series(|s| s.best)),
+                ("mean", series(|s| s.mean)),
+                ("median", series(|s| s.median)),
+                ("worst", series(|s| s.worst)),
+            ],
+            filename,
+            resolution,
+        )
+    }
+}
+
+/// Persisted state of a Cosyne run
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    config: Config,
+    pop: Population,
+    generation: usize,
+    champion_fit_history: Vec<f64>,
+    champion: (ANN, f64),
+    pareto_front: Vec<(ANN, Vec<f64>)>,
+    novelty_archive: Vec<Vec<f64>>,
+    map_elites: Option<MapElites>,
+    restart_generations: Vec<usize>,
+    champion_samples: usize,
+    history: Vec<GenerationStats>,
+    evaluations: usize,
+}
+
+/// Environment the networks are evaluated in
+enum Evaluator {
+    Single(Box<dyn Environment>),
+    MultiObjective(Box<dyn MultiObjectiveEnvironment>),
+    Behavior(Box<dyn BehaviorEnvironment>),
+}
+
+impl Evaluator {
+    /// Return the objectives of a network and its behavior,
+    /// which is empty unless the environment describes behaviors
+    fn evaluate(&self, nn: &mut ANN) -> (Vec<f64>, Vec<f64>) {
+        match self {
+            Evaluator::Single(env) => (vec![env.evaluate(nn)], vec![]),
+            Evaluator::MultiObjective(env) => (env.evaluate(nn), vec![]),
+            Evaluator::Behavior(env) => {
+                let (fit, behavior) = env.evaluate(nn);
+                (vec![fit], behavior)
+            }
+        }
+    }
+
+    /// Check that the environment provides what the selection needs
+    fn check_selection(&self, selection: Selection) -> Result<(), CosyneError> {
+        match selection {
+            Selection::Novelty(_) if !self.describes_behavior() => {
+                Err(CosyneError::MissingBehavior)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Return true if the environment returns behavior descriptors
+    fn describes_behavior(&self) -> bool {
+        matches!(self, Evaluator::Behavior(_))
+    }
+}
 
-        plot_values(&self.champion_fit_history, filename, resolution)
+/// Outcome of evaluating a single network
+pub(crate) struct Evaluation {
+    net: ANN,
+    // aggregated value of every objective over all evaluations
+    objectives: Vec<f64>,
+    // aggregated behavior descriptor over all evaluations
+    behavior: Vec<f64>,
+    // sum of the objectives
+    fitness: f64,
+    // number of calls to the environment
+    evaluations: usize,
+    // number of calls that returned a non-finite objective
+    non_finite: usize,
+}
+
+/// Evaluate the objectives of a network evaluations_per_network times
+/// and aggregate the results of each objective as configured.
+/// Each evaluation is repeated as long as the NonFinitePolicy allows it
+/// and any objective is not finite
+fn evaluate_network(env: &Evaluator, nn: &ANN, config: &Config) -> Evaluation {
+    let max_attempts = config.non_finite_policy.max_attempts();
+    let mut samples: Vec<Vec<f64>> = Vec::with_capacity(config.evaluations_per_network);
+    let mut behaviors: Vec<Vec<f64>> = Vec::with_capacity(config.evaluations_per_network);
+    let mut evaluations: usize = 0;
+    let mut non_finite: usize = 0;
+    for _ in 0..config.evaluations_per_network {
+        for attempt in 1..=max_attempts {
+            // evaluate a fresh copy, so every evaluation starts without recurrent state
+            let (objs, behavior) = env.evaluate(&mut nn.clone());
+            evaluations += 1;
+            let finite = objs.iter().all(|v| v.is_finite());
+            if !finite {
+                non_finite += 1;
+            }
+            if finite || attempt == max_attempts {
+                samples.push(objs);
+                behaviors.push(behavior);
+                break;
+            }
+        }
     }
+
+    let objectives = aggregate_columns(&samples, config.fitness_aggregation);
+    Evaluation {
+        net: nn.clone(),
+        fitness: objectives.iter().sum(),
+        objectives,
+        behavior: aggregate_columns(&behaviors, config.fitness_aggregation),
+        evaluations,
+        non_finite,
+    }
+}
+
+/// Aggregate each position of the samples over all samples
+fn aggregate_columns(samples: &[Vec<f64>], aggregation: FitnessAggregation) -> Vec<f64> {
+    let len = samples.first().map_or(0, |s| s.len());
+    (0..len)
+        .map(|i| {
+            let mut values: Vec<f64> = samples.iter().map(|s| s[i]).collect();
+            aggregation.aggregate(&mut values)
+        })
+        .collect()
 }
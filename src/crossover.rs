@@ -0,0 +1,164 @@
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+
+use crate::population::DEFAULT_FIT;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the crossover operators used for recombining pairs of offspring
+pub enum Crossover {
+    /// Swap all genes before a single crossover point,
+    /// which is drawn from Normal(n / 2, 0.33 * n) with n the number of genes
+    SinglePoint,
+    /// Swap every other segment between k uniformly drawn crossover points
+    MultiPoint(usize),
+    /// Swap each gene with a probability of 0.5
+    Uniform,
+    /// Blend crossover (BLX-alpha) with the given alpha.
+    /// Each child gene is drawn uniformly from the range spanned by the parent genes,
+    /// extended by alpha times its width on both sides
+    Blend(f64),
+    /// Simulated binary crossover (SBX) with the given distribution index eta.
+    /// Large values of eta create children close to their parents
+    SimulatedBinary(f64),
+}
+
+impl Crossover {
+    /// Recombine two chromosomes of (gene, fitness) pairs in place.
+    /// Swapped genes keep their fitness, newly created genes get the default fitness
+    pub(crate) fn apply<R: Rng>(&self, p1: &mut [(f64, f64)], p2: &mut [(f64, f64)], rng: &mut R) {
+        let n = p1.len();
+        match self {
+            Crossover::SinglePoint => {
+                let d = Normal::new(n as f64 / 2.0, n as f64 * 0.33).unwrap();
+                let cross_p: f64 = d.sample(rng);
+                // clip to min and max
+                let crossover_point: usize = if cross_p < 0.0 {
+                    0
+                } else if cross_p > n as f64 {
+                    n
+                } else {
+                    cross_p.round() as usize
+                };
+                p1[..crossover_point].swap_with_slice(&mut p2[..crossover_point]);
+            }
+            Crossover::MultiPoint(k) => {
+                let mut points: Vec<usize> = (0..*k).map(|_| rng.gen_range(0..=n)).collect();
+                points.sort_unstable();
+                points.push(n);
+                // swap the segments between every other pair of points
+                let mut start: usize = 0;
+                for (idx, end) in points.iter().enumerate() {
+                    if idx % 2 == 1 {
+                        p1[start..*end].swap_with_slice(&mut p2[start..*end]);
+                    }
+                    start = *end;
+                }
+            }
+            Crossover::Uniform => {
+                for (g1, g2) in p1.iter_mut().zip(p2.iter_mut()) {
+                    if rng.gen::<bool>() {
+                        std::mem::swap(g1, g2);
+                    }
+                }
+            }
+            Crossover::Blend(alpha) => {
+                for (g1, g2) in p1.iter_mut().zip(p2.iter_mut()) {
+                    let lo = g1.0.min(g2.0);
+                    let hi = g1.0.max(g2.0);
+                    if hi == lo {
+                        // nothing to blend
+                        continue;
+                    }
+                    let ext = alpha * (hi - lo);
+                    *g1 = (rng.gen_range(lo - ext..=hi + ext), DEFAULT_FIT);
+                    *g2 = (rng.gen_range(lo - ext..=hi + ext), DEFAULT_FIT);
+                }
+            }
+            Crossover::SimulatedBinary(eta) => {
+                for (g1, g2) in p1.iter_mut().zip(p2.iter_mut()) {
+                    let u: f64 = rng.gen();
+                    let beta = if u <= 0.5 {
+                        (2.0 * u).powf(1.0 / (eta + 1.0))
+                    } else {
+                        (1.0 / (2.0 * (1.0 - u))).powf(1.0 / (eta + 1.0))
+                    };
+                    let (a, b) = (g1.0, g2.0);
+                    *g1 = (0.5 * ((1.0 + beta) * a + (1.0 - beta) * b), DEFAULT_FIT);
+                    *g2 = (0.5 * ((1.0 - beta) * a + (1.0 + beta) * b), DEFAULT_FIT);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    type Chromosome = Vec<(f64, f64)>;
+
+    fn parents() -> (Chromosome, Chromosome) {
+        let p1 = (0..20).map(|i| (i as f64, 1.0)).collect();
+        let p2 = (0..20).map(|i| (-(i as f64), 2.0)).collect();
+        (p1, p2)
+    }
+
+    /// Swapping crossovers keep the genes of both parents at every position
+    fn assert_swapped(c1: &[(f64, f64)], c2: &[(f64, f64)]) {
+        let (p1, p2) = parents();
+        for i in 0..p1.len() {
+            assert!(
+                (c1[i] == p1[i] && c2[i] == p2[i]) || (c1[i] == p2[i] && c2[i] == p1[i]),
+                "gene {} was not swapped",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn crossover_swapping() {
+        let mut rng = thread_rng();
+        for crossover in &[
+            Crossover::SinglePoint,
+            Crossover::MultiPoint(3),
+            Crossover::Uniform,
+        ] {
+            let (mut c1, mut c2) = parents();
+            crossover.apply(&mut c1, &mut c2, &mut rng);
+            assert_swapped(&c1, &c2);
+        }
+    }
+
+    #[test]
+    fn crossover_blend() {
+        let mut rng = thread_rng();
+        let (mut c1, mut c2) = parents();
+        Crossover::Blend(0.5).apply(&mut c1, &mut c2, &mut rng);
+
+        let (p1, p2) = parents();
+        for i in 1..p1.len() {
+            let width = (p1[i].0 - p2[i].0).abs();
+            for c in &[c1[i], c2[i]] {
+                assert!(c.0 >= p2[i].0 - 0.5 * width && c.0 <= p1[i].0 + 0.5 * width);
+                assert_eq!(c.1, DEFAULT_FIT);
+            }
+        }
+        // identical parent genes stay untouched
+        assert_eq!(c1[0], p1[0]);
+    }
+
+    #[test]
+    fn crossover_simulated_binary() {
+        let mut rng = thread_rng();
+        let (mut c1, mut c2) = parents();
+        Crossover::SimulatedBinary(2.0).apply(&mut c1, &mut c2, &mut rng);
+
+        // the children are symmetric around the mean of their parents
+        let (p1, p2) = parents();
+        for i in 0..p1.len() {
+            assert!((c1[i].0 + c2[i].0 - p1[i].0 - p2[i].0).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Errors caused by an invalid configuration or invalid input to the optimizer
+pub enum CosyneError {
+    /// top_ratio_to_recombine has to be in the open range (0, 1)
+    InvalidTopRatio(f64),
+    /// At least two networks have to be recombined each generation,
+    /// so pop_size * top_ratio_to_recombine has to be at least 2
+    TooFewParents {
+        /// size of the population
+        pop_size: usize,
+        /// ratio of the population that is recombined
+        top_ratio_to_recombine: f64,
+    },
+    /// A probability is not in the range [0, 1]
+    ProbabilityOutOfRange {
+        /// name of the probability
+        name: &'static str,
+        /// the invalid value
+        value: f64,
+    },
+    /// A strength, scale or other parameter is negative or not finite
+    InvalidParameter {
+        /// name of the parameter
+        name: &'static str,
+        /// the invalid value
+        value: f64,
+    },
+    /// A count, e.g. of evaluations or neighbours, is 0
+    MustBePositive {
+        /// name of the count
+        name: &'static str,
+    },
+    /// The number of genes does not match the number of genes of the network
+    GeneCountMismatch {
+        /// number of genes of the network
+        expected: usize,
+        /// number of genes that were supplied
+        actual: usize,
+    },
+    /// The bounds of a MapElites behavior dimension or of an Initializer are not finite
+    /// or the upper bound is not greater than the lower bound
+    InvalidBounds {
+        /// lower bound
+        low: f64,
+        /// upper bound
+        high: f64,
+    },
+    /// The length of a behavior descriptor does not match the dimensions of a MapElites archive
+    BehaviorLengthMismatch {
+        /// number of behavior dimensions of the archive
+        expected: usize,
+        /// length of the behavior descriptor
+        actual: usize,
+    },
+    /// A layer index is not smaller than the number of layers of the network
+    LayerOutOfRange {
+        /// index of the layer
+        layer: usize,
+        /// number of layers of the network
+        num_layers: usize,
+    },
+    /// The environment returned a NaN or infinite fitness with NonFinitePolicy::Abort
+    NonFiniteFitness {
+        /// index of the network in the population
+        network: usize,
+        /// the non-finite fitness
+        fitness: f64,
+    },
+    /// Selection::Novelty or a MapElites archive was used with an environment
+    /// without behavior descriptors
+    MissingBehavior,
+    /// StopCondition::Any or StopCondition::All was given an empty list of conditions
+    EmptyStopCondition,
+    /// A RestartStrategy sets neither stagnation nor min_diversity and would never restart
+    NoRestartTrigger,
+}
+
+impl fmt::Display for CosyneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CosyneError::InvalidTopRatio(r) => write!(
+                f,
+                "top_ratio_to_recombine has to be greater than 0 and less than 1, got {}",
+                r
+            ),
+            CosyneError::TooFewParents {
+                pop_size,
+                top_ratio_to_recombine,
+            } => write!(
+                f,
+                "at least 2 networks have to be recombined, but pop_size {} with \
+                 top_ratio_to_recombine {} only selects {}",
+                pop_size,
+                top_ratio_to_recombine,
+                (*pop_size as f64 * top_ratio_to_recombine).floor()
+            ),
+            CosyneError::ProbabilityOutOfRange { name, value } => {
+                write!(f, "{} has to be in the range [0, 1], got {}", name, value)
+            }
+            CosyneError::InvalidParameter { name, value } => {
+                write!(
+                    f,
+                    "{} has to be finite and not negative, got {}",
+                    name, value
+                )
+            }
+            CosyneError::MustBePositive { name } => write!(f, "{} must be at least 1", name),
+            CosyneError::GeneCountMismatch { expected, actual } => write!(
+                f,
+                "the network has {} genes, but {} were supplied",
+                expected, actual
+            ),
+            CosyneError::InvalidBounds { low, high } => write!(
+                f,
+                "the bounds have to be finite with the upper bound greater than the lower bound, \
+                 got [{}, {}]",
+                low, high
+            ),
+            CosyneError::BehaviorLengthMismatch { expected, actual } => write!(
+                f,
+                "the archive has {} behavior dimensions, but the behavior has {} values",
+                expected, actual
+            ),
+            CosyneError::LayerOutOfRange { layer, num_layers } => write!(
+                f,
+                "layer {} does not exist, the network has {} layers",
+                layer, num_layers
+            ),
+            CosyneError::NonFiniteFitness { network, fitness } => write!(
+                f,
+                "network {} was evaluated with the non-finite fitness {}",
+                network, fitness
+            ),
+            CosyneError::EmptyStopCondition => {
+                write!(f, "Any and All stop conditions need at least one condition")
+            }
+            CosyneError::NoRestartTrigger => {
+                write!(f, "a restart strategy needs stagnation or min_diversity")
+            }
+            CosyneError::MissingBehavior => write!(
+                f,
+                "novelty selection and MAP-Elites require a BehaviorEnvironment, \
+                 see Cosyne::new_with_behavior"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CosyneError {}
@@ -0,0 +1,429 @@
+//! Hyperparameter sweeps over Config fields, running every configuration for several seeds
+//! and summarizing the final fitness and the evaluations needed to reach a target fitness
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use failure::Error;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    stats::{mean_std_dev, median},
+    Config, Cosyne, CosyneError, Environment, StopCondition, ANN,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Enumerate the Config fields that can be searched over
+pub enum Param {
+    /// Config::pop_size, values are rounded to the nearest integer
+    PopSize,
+    /// Config::top_ratio_to_recombine
+    TopRatioToRecombine,
+    /// Config::mutation_prob
+    MutationProb,
+    /// Config::mutation_strength
+    MutationStrength,
+    /// Config::perturb_prob
+    PerturbProb,
+}
+
+impl Param {
+    /// Return the name of the Config field
+    pub fn name(&self) -> &'static str {
+        match self {
+            Param::PopSize => "pop_size",
+            Param::TopRatioToRecombine => "top_ratio_to_recombine",
+            Param::MutationProb => "mutation_prob",
+            Param::MutationStrength => "mutation_strength",
+            Param::PerturbProb => "perturb_prob",
+        }
+    }
+
+    /// Set the Config field to the value
+    fn apply(&self, config: &mut Config, value: f64) {
+        match self {
+            Param::PopSize => config.pop_size = value.round().max(0.0) as usize,
+            Param::TopRatioToRecombine => config.top_ratio_to_recombine = value,
+            Param::MutationProb => config.mutation_prob = value,
+            Param::MutationStrength => config.mutation_strength = value,
+            Param::PerturbProb => config.perturb_prob = value,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Enumerate the ways of choosing the configurations of an Experiment
+pub enum Search {
+    /// Every combination of the given values of each parameter
+    Grid(Vec<(Param, Vec<f64>)>),
+    /// The given number of configurations,
+    /// with each parameter drawn uniformly from its range [low, high]
+    Random {
+        /// parameters with their lowest and highest value
+        ranges: Vec<(Param, f64, f64)>,
+        /// number of configurations
+        samples: usize,
+        /// seed for drawing the configurations
+        seed: u64,
+    },
+}
+
+impl Search {
+    /// Return the parameter values of every configuration
+    fn candidates(&self) -> Vec<Vec<(Param, f64)>> {
+        match self {
+            Search::Grid(axes) => {
+                let mut candidates: Vec<Vec<(Param, f64)>> = vec![vec![]];
+                for (param, values) in axes {
+                    candidates = candidates
+                        .iter()
+                        .flat_map(|c| {
+                            values.iter().map(move |v| {
+                                let mut c = c.clone();
+                                c.push((*param, *v));
+                                c
+                            })
+                        })
+                        .collect();
+                }
+                candidates
+            }
+            Search::Random {
+                ranges,
+                samples,
+                seed,
+            } => {
+                let mut rng = ChaCha8Rng::seed_from_u64(*seed);
+                (0..*samples)
+                    .map(|_| {
+                        ranges
+                            .iter()
+                            .map(|(param, low, high)| {
+                                (*param, low + rng.gen::<f64>() * (high - low))
+                            })
+                            .collect()
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Location and spread of the results of several runs
+pub struct Summary {
+    /// mean value
+    pub mean: f64,
+    /// median value
+    pub median: f64,
+    /// first quartile
+    pub q1: f64,
+    /// third quartile
+    pub q3: f64,
+}
+
+impl Summary {
+    /// Summarize the values, which are all NaN if there are no values
+    fn new(vals: &[f64]) -> Self {
+        let mut sorted: Vec<f64> = vals.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        Self {
+            mean: mean_std_dev(vals).0,
+            median: median(&sorted),
+            q1: quantile(&sorted, 0.25),
+            q3: quantile(&sorted, 0.75),
+        }
+    }
+
+    /// Interquartile range q3 - q1
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+}
+
+/// Return the q-quantile of already sorted values, interpolating linearly between them
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (pos - lo as f64) * (sorted[hi] - sorted[lo])
+}
+
+#[derive(Debug, Clone)]
+/// Outcome of a single run of an Experiment
+pub struct RunResult {
+    /// seed of the run
+    pub seed: u64,
+    /// final champion fitness
+    pub fitness: f64,
+    /// total number of network evaluations
+    pub evaluations: usize,
+    /// number of evaluations until the champion reached the target fitness, if it did
+    pub evaluations_to_target: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+/// Outcome of all runs of one configuration of an Experiment
+pub struct ConfigResult {
+    /// values of the searched parameters
+    pub params: Vec<(Param, f64)>,
+    /// the complete configuration
+    pub config: Config,
+    /// every run, one per seed
+    pub runs: Vec<RunResult>,
+}
+
+impl ConfigResult {
+    /// Summary of the final champion fitness of all runs
+    pub fn fitness(&self) -> Summary {
+        let vals: Vec<f64> = self.runs.iter().map(|r| r.fitness).collect();
+        Summary::new(&vals)
+    }
+
+    /// Summary of the evaluations needed to reach the target fitness
+    /// of the runs which reached it
+    pub fn evaluations_to_target(&self) -> Summary {
+        let vals: Vec<f64> = self
+            .runs
+            .iter()
+            .filter_map(|r| r.evaluations_to_target)
+            .map(|e| e as f64)
+            .collect();
+        Summary::new(&vals)
+    }
+
+    /// Ratio of runs that reached the target fitness
+    pub fn success_rate(&self) -> f64 {
+        let successes = self
+            .runs
+            .iter()
+            .filter(|r| r.evaluations_to_target.is_some())
+            .count();
+        successes as f64 / self.runs.len() as f64
+    }
+}
+
+/// Runs every configuration of a Search for several seeds
+pub struct Experiment<F: Fn(u64) -> Box<dyn Environment>> {
+    config: Config,
+    nn: ANN,
+    env_factory: F,
+    stop: StopCondition,
+    search: Search,
+    seeds: usize,
+    target: Option<f64>,
+}
+
+impl<F: Fn(u64) -> Box<dyn Environment>> Experiment<F> {
+    /// Create a new experiment starting from a base config and network,
+    /// creating a new environment for every run from its seed with env_factory.
+    /// Each run ends once the stop condition is met.
+    /// By default only the base config is run for 10 seeds
+    pub fn new(config: Config, nn: ANN, env_factory: F, stop: StopCondition) -> Self {
+        Self {
+            config,
+            nn,
+            env_factory,
+            stop,
+            search: Search::Grid(vec![]),
+            seeds: 10,
+            target: None,
+        }
+    }
+
+    /// Set the configurations to search over
+    pub fn search(mut self, search: Search) -> Self {
+        self.search = search;
+        self
+    }
+
+    /// Set the number of runs per configuration, which use the seeds 0..seeds
+    pub fn seeds(mut self, seeds: usize) -> Self {
+        self.seeds = seeds;
+        self
+    }
+
+    /// Set the fitness for measuring the evaluations to target.
+    /// The stop condition is not changed, so add TargetFitness to it to save evaluations
+    pub fn target_fitness(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Run every configuration for every seed.
+    /// Returns an error if a configuration is invalid or a run fails
+    pub fn run(&self) -> Result<Vec<ConfigResult>, CosyneError> {
+        let mut results: Vec<ConfigResult> = vec![];
+        for params in self.search.candidates() {
+            let mut config = self.config;
+            for (param, value) in &params {
+                param.apply(&mut config, *value);
+            }
+            config.validate()?;
+
+            let mut runs: Vec<RunResult> = Vec::with_capacity(self.seeds);
+            for seed in 0..self.seeds as u64 {
+                config.seed = Some(seed);
+                let env = (self.env_factory)(seed);
+                let mut cosyne = Cosyne::try_new(env, self.nn.clone(), config)?;
+                let report = cosyne.run(self.stop.clone())?;
+                let evaluations_to_target = self.target.and_then(|target| {
+                    cosyne
+                        .history()
+                        .iter()
+                        .find(|s| s.champion >= target)
+                        .map(|s| s.evaluations)
+                });
+                info!(
+                    "{:?}, seed {}: fitness {:.4}",
+                    params, seed, report.champion.1
+                );
+                runs.push(RunResult {
+                    seed,
+                    fitness: report.champion.1,
+                    evaluations: report.evaluations,
+                    evaluations_to_target,
+                });
+            }
+            results.push(ConfigResult {
+                params,
+                config,
+                runs,
+            });
+        }
+
+        Ok(results)
+    }
+}
+
+/// Write one line per configuration with the summaries of its runs
+pub fn write_summary_csv<P: AsRef<Path>>(results: &[ConfigResult], path: P) -> Result<(), Error> {
+    let mut w = BufWriter::new(File::create(path)?);
+    writeln!(
+        w,
+        "{}runs,success_rate,fitness_mean,fitness_median,fitness_q1,fitness_q3,fitness_iqr,\
+         evaluations_to_target_mean,evaluations_to_target_median,\
+         evaluations_to_target_q1,evaluations_to_target_q3,evaluations_to_target_iqr",
+        param_header(results)
+    )?;
+    for r in results {
+        let (f, e) = (r.fitness(), r.evaluations_to_target());
+        writeln!(
+            w,
+            "{}{},{},{},{},{},{},{},{},{},{},{},{}",
+            param_values(r),
+            r.runs.len(),
+            r.success_rate(),
+            f.mean,
+            f.median,
+            f.q1,
+            f.q3,
+            f.iqr(),
+            e.mean,
+            e.median,
+            e.q1,
+            e.q3,
+            e.iqr()
+        )?;
+    }
+    w.flush()?;
+
+    Ok(())
+}
+
+/// Write one line per run, for analyzing the results with other tools
+pub fn write_runs_csv<P: AsRef<Path>>(results: &[ConfigResult], path: P) -> Result<(), Error> {
+    let mut w = BufWriter::new(File::create(path)?);
+    writeln!(
+        w,
+        "{}seed,fitness,evaluations,evaluations_to_target",
+        param_header(results)
+    )?;
+    for r in results {
+        for run in &r.runs {
+            writeln!(
+                w,
+                "{}{},{},{},{}",
+                param_values(r),
+                run.seed,
+                run.fitness,
+                run.evaluations,
+                run.evaluations_to_target
+                    .map_or(String::new(), |e| e.to_string())
+            )?;
+        }
+    }
+    w.flush()?;
+
+    Ok(())
+}
+
+/// Column names of the searched parameters, followed by a comma
+fn param_header(results: &[ConfigResult]) -> String {
+    results.first().map_or(String::new(), |r| {
+        r.params
+            .iter()
+            .map(|(p, _)| format!("{},", p.name()))
+            .collect()
+    })
+}
+
+/// Values of the searched parameters, followed by a comma
+fn param_values(result: &ConfigResult) -> String {
+    result
+        .params
+        .iter()
+        .map(|(_, v)| format!("{},", v))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_candidates() {
+        let search = Search::Grid(vec![
+            (Param::MutationProb, vec![0.1, 0.2]),
+            (Param::PerturbProb, vec![0.3, 0.4, 0.5]),
+        ]);
+        let candidates = search.candidates();
+        assert_eq!(candidates.len(), 6);
+        assert_eq!(
+            candidates[1],
+            vec![(Param::MutationProb, 0.1), (Param::PerturbProb, 0.4)]
+        );
+        assert_eq!(Search::Grid(vec![]).candidates(), vec![vec![]]);
+    }
+
+    #[test]
+    fn random_candidates() {
+        let search = Search::Random {
+            ranges: vec![(Param::MutationStrength, 0.5, 2.0)],
+            samples: 20,
+            seed: 0,
+        };
+        let candidates = search.candidates();
+        assert_eq!(candidates.len(), 20);
+        assert!(candidates
+            .iter()
+            .all(|c| (0.5..=2.0).contains(&c[0].1) && c[0].0 == Param::MutationStrength));
+    }
+
+    #[test]
+    fn summary() {
+        let s = Summary::new(&[4.0, 1.0, 3.0, 2.0, 5.0]);
+        assert_eq!(s.mean, 3.0);
+        assert_eq!(s.median, 3.0);
+        assert_eq!(s.q1, 2.0);
+        assert_eq!(s.q3, 4.0);
+        assert_eq!(s.iqr(), 2.0);
+        assert!(Summary::new(&[]).mean.is_nan());
+    }
+}
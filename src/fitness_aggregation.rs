@@ -0,0 +1,44 @@
+use crate::stats::median;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the ways of combining repeated evaluations of a network into a single fitness
+pub enum FitnessAggregation {
+    /// Mean of all evaluations
+    #[default]
+    Mean,
+    /// Median of all evaluations, which is robust against outliers
+    Median,
+}
+
+impl FitnessAggregation {
+    /// Combine the fitness samples of one network.
+    /// Non-finite samples are passed on, so they are handled by the NonFinitePolicy
+    pub(crate) fn aggregate(&self, samples: &mut [f64]) -> f64 {
+        if let Some(f) = samples.iter().find(|f| !f.is_finite()) {
+            return *f;
+        }
+        match self {
+            FitnessAggregation::Mean => samples.iter().sum::<f64>() / samples.len() as f64,
+            FitnessAggregation::Median => {
+                samples.sort_by(|a, b| a.total_cmp(b));
+                median(samples)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate() {
+        let mut samples = vec![1.0, 10.0, 2.0];
+        assert_eq!(FitnessAggregation::Mean.aggregate(&mut samples), 13.0 / 3.0);
+        assert_eq!(FitnessAggregation::Median.aggregate(&mut samples), 2.0);
+
+        let mut samples = vec![1.0, f64::NAN];
+        assert!(FitnessAggregation::Median.aggregate(&mut samples).is_nan());
+    }
+}
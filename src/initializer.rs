@@ -0,0 +1,176 @@
+use na::DMatrix as Matrix;
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+
+use crate::{config::check_parameter, CosyneError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the schemes for drawing the initial weights and biases of a layer
+pub enum Initializer {
+    /// Uniform distribution in the range [low, high]
+    Uniform(f64, f64),
+    /// Normal distribution with the given mean and standard deviation
+    Gaussian(f64, f64),
+    /// Xavier / Glorot uniform initialization
+    /// in the range +-sqrt(6 / (fan_in + fan_out))
+    Xavier,
+    /// He initialization, normal distribution with standard deviation sqrt(2 / fan_in)
+    He,
+    /// Random orthogonal matrix, so rows or columns are orthonormal, whichever are fewer
+    Orthogonal,
+    /// All values set to zero, e.g. for biases
+    Zeros,
+    /// Latin hypercube sampling in the range [low, high] across the whole population.
+    /// The range is split into pop_size equally sized strata,
+    /// and every stratum is used by exactly one network for each gene.
+    /// A single network on its own is initialized uniformly in [low, high]
+    LatinHypercube(f64, f64),
+}
+
+impl Default for Initializer {
+    fn default() -> Self {
+        Initializer::Uniform(-1.0, 1.0)
+    }
+}
+
+impl Initializer {
+    /// Check that the bounds and standard deviation are valid
+    pub(crate) fn validate(&self) -> Result<(), CosyneError> {
+        match *self {
+            Initializer::Uniform(low, high) | Initializer::LatinHypercube(low, high) => {
+                if low.is_finite() && high.is_finite() && high > low {
+                    Ok(())
+                } else {
+                    Err(CosyneError::InvalidBounds { low, high })
+                }
+            }
+            Initializer::Gaussian(mean, std_dev) => {
+                if !mean.is_finite() {
+                    return Err(CosyneError::InvalidParameter {
+                        name: "gaussian initializer mean",
+                        value: mean,
+                    });
+                }
+                check_parameter("gaussian initializer std_dev", std_dev)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Draw a matrix of the given shape.
+    /// fan_in and fan_out are the input and output lengths of the layer the values belong to
+    pub(crate) fn sample_matrix<R: Rng>(
+        &self,
+        nrows: usize,
+        ncols: usize,
+        fan_in: usize,
+        fan_out: usize,
+        rng: &mut R,
+    ) -> Matrix<f64> {
+        match self {
+            Initializer::Uniform(low, high) | Initializer::LatinHypercube(low, high) => {
+                Matrix::from_fn(nrows, ncols, |_, _| low + rng.gen::<f64>() * (high - low))
+            }
+            Initializer::Gaussian(mean, std_dev) => {
+                let d = Normal::new(*mean, *std_dev).unwrap();
+                Matrix::from_fn(nrows, ncols, |_, _| d.sample(rng))
+            }
+            Initializer::Xavier => {
+                let limit = (6.0 / (fan_in + fan_out).max(1) as f64).sqrt();
+                Matrix::from_fn(nrows, ncols, |_, _| (rng.gen::<f64>() * 2.0 - 1.0) * limit)
+            }
+            Initializer::He => {
+                let d = Normal::new(0.0, (2.0 / fan_in.max(1) as f64).sqrt()).unwrap();
+                Matrix::from_fn(nrows, ncols, |_, _| d.sample(rng))
+            }
+            Initializer::Orthogonal => orthogonal(nrows, ncols, rng),
+            Initializer::Zeros => Matrix::zeros(nrows, ncols),
+        }
+    }
+}
+
+/// Random orthogonal matrix from the QR decomposition of a gaussian matrix
+fn orthogonal<R: Rng>(nrows: usize, ncols: usize, rng: &mut R) -> Matrix<f64> {
+    if nrows * ncols == 0 {
+        return Matrix::zeros(nrows, ncols);
+    }
+    // decompose the tall orientation, so q has orthonormal columns
+    let (rows, cols) = (nrows.max(ncols), nrows.min(ncols));
+    let d = Normal::new(0.0, 1.0).unwrap();
+    let a: Matrix<f64> = Matrix::from_fn(rows, cols, |_, _| d.sample(rng));
+    let qr = a.qr();
+    let mut q = qr.q();
+    // make the decomposition unique, so q is uniformly distributed
+    let r = qr.r();
+    for c in 0..cols {
+        if r[(c, c)] < 0.0 {
+            q.column_mut(c).neg_mut();
+        }
+    }
+
+    if nrows >= ncols {
+        q
+    } else {
+        q.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn initializer_ranges() {
+        let mut rng = thread_rng();
+
+        let m = Initializer::Uniform(0.5, 2.0).sample_matrix(10, 10, 10, 10, &mut rng);
+        assert!(m.iter().all(|v| (0.5..=2.0).contains(v)));
+
+        let m = Initializer::Xavier.sample_matrix(10, 20, 20, 10, &mut rng);
+        let limit = (6.0_f64 / 30.0).sqrt();
+        assert!(m.iter().all(|v| v.abs() <= limit));
+
+        let m = Initializer::Zeros.sample_matrix(3, 4, 4, 3, &mut rng);
+        assert_eq!(m, Matrix::zeros(3, 4));
+    }
+
+    #[test]
+    fn initializer_validate() {
+        assert!(Initializer::Gaussian(-1.0, 0.5).validate().is_ok());
+        assert_eq!(
+            Initializer::Gaussian(0.0, -1.0).validate(),
+            Err(CosyneError::InvalidParameter {
+                name: "gaussian initializer std_dev",
+                value: -1.0
+            })
+        );
+        assert!(Initializer::Gaussian(f64::NAN, 1.0).validate().is_err());
+        assert_eq!(
+            Initializer::Uniform(1.0, -1.0).validate(),
+            Err(CosyneError::InvalidBounds {
+                low: 1.0,
+                high: -1.0
+            })
+        );
+        assert!(Initializer::LatinHypercube(0.0, f64::INFINITY)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn initializer_orthogonal() {
+        let mut rng = thread_rng();
+
+        // tall matrix has orthonormal columns
+        let m = Initializer::Orthogonal.sample_matrix(5, 3, 3, 5, &mut rng);
+        assert_eq!(m.shape(), (5, 3));
+        assert!((m.transpose() * &m - Matrix::identity(3, 3)).norm() < 1e-9);
+
+        // wide matrix has orthonormal rows
+        let m = Initializer::Orthogonal.sample_matrix(2, 4, 4, 2, &mut rng);
+        assert_eq!(m.shape(), (2, 4));
+        assert!((&m * m.transpose() - Matrix::identity(2, 2)).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,292 @@
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    config::check_count, stop_condition::Progress, Cosyne, CosyneError, RunReport, StopCondition,
+    ANN,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the ways islands send their migrants to each other
+pub enum MigrationTopology {
+    /// Island i sends its migrants to island i + 1, the last island to the first
+    #[default]
+    Ring,
+    /// Every island sends its migrants to every other island
+    FullyConnected,
+    /// Every island sends its migrants to another island chosen at random at each migration
+    Random,
+}
+
+impl MigrationTopology {
+    /// Return the indices of the islands receiving the migrants of island i
+    fn destinations<R: Rng>(&self, i: usize, num_islands: usize, rng: &mut R) -> Vec<usize> {
+        if num_islands < 2 {
+            return vec![];
+        }
+        match self {
+            MigrationTopology::Ring => vec![(i + 1) % num_islands],
+            MigrationTopology::FullyConnected => (0..num_islands).filter(|d| *d != i).collect(),
+            MigrationTopology::Random => {
+                // draw from all other islands
+                let d = rng.gen_range(0..num_islands - 1);
+                vec![if d >= i { d + 1 } else { d }]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Outcome of an island model run
+pub struct IslandReport {
+    /// Reasons, generations, evaluations and wall time of the whole run
+    /// and the fittest champion of all islands
+    pub global: RunReport,
+    /// The champion of every island and its fitness
+    pub island_champions: Vec<(ANN, f64)>,
+}
+
+/// Evolves several Cosyne optimizers side by side
+/// and migrates their best networks between them every few generations.
+/// Each island keeps its own environment, config and network population
+pub struct IslandModel {
+    islands: Vec<Cosyne>,
+    topology: MigrationTopology,
+    migration_interval: usize,
+    migrants: usize,
+    rng: ChaCha8Rng,
+    generation: usize,
+    // fitness of the global champion after each generation
+    champion_fit_history: Vec<f64>,
+    start: Instant,
+}
+
+impl IslandModel {
+    /// Create an IslandModelBuilder from the given islands, which may use different configs,
+    /// but have to use the same network topology.
+    /// By default the best network of each island migrates along a ring every 5 generations
+    pub fn builder(islands: Vec<Cosyne>) -> IslandModelBuilder {
+        IslandModelBuilder {
+            islands,
+            topology: MigrationTopology::Ring,
+            migration_interval: 5,
+            migrants: 1,
+            rng: ChaCha8Rng::from_entropy(),
+        }
+    }
+
+    /// Evolve every island by one generation
+    /// and migrate the best networks if the migration interval has passed.
+    /// Migrants replace the least fit networks of their destination after evaluation
+    /// and before breeding, see Cosyne::immigrate.
+    /// Returns an error if an island could not be evolved,
+    /// in which case no island records the generation
+    pub fn evolve(&mut self) -> Result<(), CosyneError> {
+        let evaluated = self
+            .islands
+            .iter_mut()
+            .map(|island| island.evaluate_checked())
+            .collect::<Result<Vec<_>, CosyneError>>()?;
+        for (island, evaluated) in self.islands.iter_mut().zip(evaluated) {
+            island.record_generation(evaluated);
+        }
+        if (self.generation + 1) % self.migration_interval == 0 {
+            self.migrate()?;
+        }
+        for island in &mut self.islands {
+            island.breed();
+        }
+        self.champion_fit_history.push(self.champion().1);
+
+        info!(
+            "gen {}, global champion fitness: {:.4}",
+            self.generation,
+            self.champion().1
+        );
+        self.generation += 1;
+
+        Ok(())
+    }
+
+    /// Send the best networks of every island to its destinations.
+    /// The islands were checked to use the same network topology, so immigration cannot fail.
+    /// All migrants are chosen before any island receives immigrants
+    fn migrate(&mut self) -> Result<(), CosyneError> {
+        let n = self.islands.len();
+        let mut immigrants: Vec<Vec<ANN>> = vec![vec![]; n];
+        for i in 0..n {
+            let emigrants = self.islands[i].emigrants(self.migrants);
+            for d in self.topology.destinations(i, n, &mut self.rng) {
+                immigrants[d].extend(emigrants.iter().cloned());
+            }
+        }
+        for (island, immigrants) in self.islands.iter_mut().zip(immigrants) {
+            island.immigrate(&immigrants)?;
+        }
+        debug!("gen {}, migrated between {} islands", self.generation, n);
+
+        Ok(())
+    }
+
+    /// Evolve until the stop condition is met by the whole model and report why the run stopped.
+    /// Generations are counted by the model, evaluations are summed over all islands
+    /// and the champion is the fittest champion of all islands.
+    /// Returns an error if the stop condition contains an empty Any or All or Stagnation(0),
+    /// or a generation could not be evolved, see evolve
+    pub fn run(&mut self, stop: StopCondition) -> Result<IslandReport, CosyneError> {
+        stop.validate()?;
+        let t0 = Instant::now();
+        let first_generation = self.generation;
+        loop {
+            let progress = Progress {
+                generation: self.generation,
+                evaluations: self.evaluations(),
+                champion_fitness: self.champion().1,
+                champion_fit_history: &self.champion_fit_history,
+                elapsed: self.elapsed(),
+            };
+            if let Some(reasons) = stop.check(&progress) {
+                return Ok(IslandReport {
+                    global: RunReport {
+                        reasons,
+                        generations: self.generation - first_generation,
+                        evaluations: self.evaluations(),
+                        elapsed: t0.elapsed(),
+                        champion: self.champion().clone(),
+                    },
+                    island_champions: self.island_champions(),
+                });
+            }
+            self.evolve()?;
+        }
+    }
+
+    /// Get the fittest champion of all islands and its fitness
+    pub fn champion(&self) -> &(ANN, f64) {
+        self.islands
+            .iter()
+            .map(|island| island.champion())
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("an island model has at least one island")
+    }
+
+    /// Get the champion of every island and its fitness
+    pub fn island_champions(&self) -> Vec<(ANN, f64)> {
+        self.islands
+            .iter()
+            .map(|island| island.champion().clone())
+            .collect()
+    }
+
+    /// Get the islands, e.g. to inspect their history
+    pub fn islands(&self) -> &[Cosyne] {
+        &self.islands
+    }
+
+    /// Get the global champion fitness after each generation
+    pub fn champion_fit_history(&self) -> &[f64] {
+        &self.champion_fit_history
+    }
+
+    /// Get the number of generations evolved so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Get the total number of network evaluations of all islands so far
+    pub fn evaluations(&self) -> usize {
+        self.islands.iter().map(|island| island.evaluations()).sum()
+    }
+
+    /// Get the wall time elapsed since the island model was created
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Builder for a validated IslandModel, created by IslandModel::builder
+pub struct IslandModelBuilder {
+    islands: Vec<Cosyne>,
+    topology: MigrationTopology,
+    migration_interval: usize,
+    migrants: usize,
+    rng: ChaCha8Rng,
+}
+
+impl IslandModelBuilder {
+    /// Topology along which the migrants are sent, default MigrationTopology::Ring
+    pub fn topology(mut self, topology: MigrationTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Number of generations between migrations, at least 1, default 5
+    pub fn migration_interval(mut self, generations: usize) -> Self {
+        self.migration_interval = generations;
+        self
+    }
+
+    /// Number of networks each island sends to each of its destinations, at least 1, default 1
+    pub fn migrants(mut self, migrants: usize) -> Self {
+        self.migrants = migrants;
+        self
+    }
+
+    /// Seed the choice of destinations of MigrationTopology::Random,
+    /// the islands are seeded by their own config
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    /// Validate the options and return the IslandModel.
+    /// Returns an error if there are no islands, the migration interval or the number of migrants
+    /// is 0 or the islands use different network topologies
+    pub fn build(self) -> Result<IslandModel, CosyneError> {
+        check_count("number of islands", self.islands.len())?;
+        check_count("migration_interval", self.migration_interval)?;
+        check_count("migrants", self.migrants)?;
+        let expected = self.islands[0].champion().0.num_genes();
+        for island in &self.islands[1..] {
+            let actual = island.champion().0.num_genes();
+            if actual != expected {
+                return Err(CosyneError::GeneCountMismatch { expected, actual });
+            }
+        }
+        Ok(IslandModel {
+            islands: self.islands,
+            topology: self.topology,
+            migration_interval: self.migration_interval,
+            migrants: self.migrants,
+            rng: self.rng,
+            generation: 0,
+            champion_fit_history: vec![],
+            start: Instant::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn destinations() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let ring = MigrationTopology::Ring;
+        assert_eq!(ring.destinations(0, 3, &mut rng), vec![1]);
+        assert_eq!(ring.destinations(2, 3, &mut rng), vec![0]);
+        assert!(ring.destinations(0, 1, &mut rng).is_empty());
+
+        let full = MigrationTopology::FullyConnected;
+        assert_eq!(full.destinations(1, 3, &mut rng), vec![0, 2]);
+
+        for _ in 0..20 {
+            let d = MigrationTopology::Random.destinations(1, 3, &mut rng);
+            assert!(d == vec![0] || d == vec![2]);
+        }
+    }
+}
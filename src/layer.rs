@@ -2,9 +2,10 @@ use rand::{thread_rng, Rng};
 
 use na::DMatrix as Matrix;
 
-use crate::Activation;
+use crate::{Activation, Initializer};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Layer {
     pub(crate) input_len: usize,
     pub(crate) output_len: usize,
@@ -12,51 +13,146 @@ pub struct Layer {
     pub(crate) activation: Activation,
     weights: Matrix<f64>,
     biases: Matrix<f64>,
-    act: Matrix<f64>, // activations
-    act_func: fn(f64) -> f64,
-    net: Matrix<f64>,
+    // output_len x output_len weights feeding the previous output back into the layer
+    recurrent_weights: Option<Matrix<f64>>,
+    // output of the previous forward pass, only used by recurrent layers
+    state: Matrix<f64>,
+    // scheme for (re-)initializing the weights and recurrent weights
+    pub(crate) weight_init: Initializer,
+    // scheme for (re-)initializing the biases
+    pub(crate) bias_init: Initializer,
 }
 
 impl Layer {
     /// Create a new Layer with given input and output length and random weight and biases
     pub fn new(input_len: usize, output_len: usize, activation: Activation) -> Self {
-        let weights = Matrix::from_vec(
-            output_len,
-            input_len,
-            rand_vec_uniform(input_len * output_len),
-        );
-        let biases = Matrix::from_vec(output_len, 1, rand_vec_uniform(output_len));
-        let act_func = activation.get_func();
-        Self {
+        Layer::build(input_len, output_len, activation, false)
+    }
+
+    /// Create a new Elman-style self-recurrent Layer, which feeds its previous output
+    /// back into itself through a separate recurrent weight matrix
+    pub fn new_recurrent(input_len: usize, output_len: usize, activation: Activation) -> Self {
+        Layer::build(input_len, output_len, activation, true)
+    }
+
+    /// Create a new Layer, initialized with the default initializers
+    fn build(input_len: usize, output_len: usize, activation: Activation, recurrent: bool) -> Self {
+        let recurrent_len = if recurrent {
+            output_len * output_len
+        } else {
+            0
+        };
+        let mut layer = Self {
             input_len,
             output_len,
             activation,
-            gene_len: output_len * input_len + output_len,
-            weights,
-            biases,
-            act: Matrix::from_vec(input_len, 1, vec![0.0; input_len]),
-            act_func,
-            net: Matrix::from_vec(output_len, 1, vec![0.0; output_len]),
+            gene_len: output_len * input_len + output_len + recurrent_len,
+            weights: Matrix::zeros(output_len, input_len),
+            biases: Matrix::zeros(output_len, 1),
+            recurrent_weights: if recurrent {
+                Some(Matrix::zeros(output_len, output_len))
+            } else {
+                None
+            },
+            state: Matrix::zeros(output_len, 1),
+            weight_init: Initializer::default(),
+            bias_init: Initializer::default(),
+        };
+        layer.randomize(&mut thread_rng());
+
+        layer
+    }
+
+    /// Draw new weights, biases and recurrent weights from the initializers of the layer
+    pub(crate) fn randomize<R: Rng>(&mut self, rng: &mut R) {
+        let (fan_in, fan_out) = (self.input_len, self.output_len);
+        self.weights = self
+            .weight_init
+            .sample_matrix(fan_out, fan_in, fan_in, fan_out, rng);
+        self.biases = self
+            .bias_init
+            .sample_matrix(fan_out, 1, fan_in, fan_out, rng);
+        if let Some(r) = &mut self.recurrent_weights {
+            *r = self
+                .weight_init
+                .sample_matrix(fan_out, fan_out, fan_out, fan_out, rng);
+        }
+    }
+
+    /// Set the initializers of the layer and re-initialize it with them
+    pub(crate) fn set_initializer(&mut self, weights: Initializer, biases: Initializer) {
+        self.weight_init = weights;
+        self.bias_init = biases;
+        self.randomize(&mut thread_rng());
+    }
+
+    /// Return the initializer of every gene, in the same order as genes
+    pub(crate) fn gene_initializers(&self) -> Vec<Initializer> {
+        let mut out: Vec<Initializer> = vec![self.weight_init; self.weights.len()];
+        out.extend(vec![self.bias_init; self.biases.len()]);
+        if let Some(r) = &self.recurrent_weights {
+            out.extend(vec![self.weight_init; r.len()]);
         }
+        out
     }
 
-    /// map the weight and biases in Matrices to flat vector
+    /// map the weight, biases and recurrent weights in Matrices to flat vector
     pub fn genes(&self) -> Vec<f64> {
         let mut out: Vec<f64> = Vec::new();
         out.append(&mut self.weights.as_slice().into());
         out.append(&mut self.biases.as_slice().into());
+        if let Some(r) = &self.recurrent_weights {
+            out.append(&mut r.as_slice().into());
+        }
         out
     }
 
     /// Return the number of genes in this layer
     pub fn num_genes(&self) -> usize {
-        self.weights.len() + self.biases.len()
+        self.weights.len()
+            + self.biases.len()
+            + self.recurrent_weights.as_ref().map_or(0, |r| r.len())
+    }
+
+    /// Return true if the layer feeds its previous output back into itself
+    pub fn is_recurrent(&self) -> bool {
+        self.recurrent_weights.is_some()
     }
 
     /// Forward values through one layer
     pub(crate) fn forward(&mut self, m: &Matrix<f64>) -> Matrix<f64> {
-        let net = &self.weights * m + &self.biases;
-        net.apply_into(&self.act_func)
+        let mut net = &self.weights * m + &self.biases;
+        if let Some(r) = &self.recurrent_weights {
+            net += r * &self.state;
+        }
+        let out = net.apply_into(self.activation.get_func());
+        if self.is_recurrent() {
+            self.state.copy_from(&out);
+        }
+        out
+    }
+
+    /// Clear the state of a recurrent layer, as if no inputs have been seen yet
+    pub(crate) fn reset_state(&mut self) {
+        self.state.fill(0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    /// Return the weight matrix of shape output_len x input_len
+    pub(crate) fn weights(&self) -> &Matrix<f64> {
+        &self.weights
+    }
+
+    #[cfg(feature = "serde")]
+    /// Return the bias vector of shape output_len x 1
+    pub(crate) fn biases(&self) -> &Matrix<f64> {
+        &self.biases
+    }
+
+    #[cfg(feature = "serde")]
+    /// Return the recurrent weight matrix of shape output_len x output_len, if any
+    pub(crate) fn recurrent_weights(&self) -> Option<&Matrix<f64>> {
+        self.recurrent_weights.as_ref()
     }
 
     /// Set the weights for the layer
@@ -73,30 +169,37 @@ impl Layer {
         self.biases = b;
     }
 
-    // set weights and biases of layer to the supplied genes
+    /// Set the recurrent weights of a recurrent layer
+    pub(crate) fn set_recurrent_weights(&mut self, r: Matrix<f64>) {
+        let old = self
+            .recurrent_weights
+            .as_mut()
+            .expect("layer is not recurrent");
+        assert_eq!(old.nrows(), r.nrows());
+        assert_eq!(old.ncols(), r.ncols());
+        *old = r;
+    }
+
+    // set weights, biases and recurrent weights of layer to the supplied genes
     // panics if genes.len() is wrong
     pub fn set_genes(&mut self, genes: &[f64]) {
+        assert_eq!(genes.len(), self.gene_len);
         let w_end = self.output_len * self.input_len;
+        let b_end = w_end + self.output_len;
         let weights: Matrix<f64> =
             Matrix::from_vec(self.output_len, self.input_len, genes[..w_end].to_vec());
         self.set_weights(weights);
-        let biases: Matrix<f64> = Matrix::from_vec(self.output_len, 1, genes[w_end..].to_vec());
+        let biases: Matrix<f64> =
+            Matrix::from_vec(self.output_len, 1, genes[w_end..b_end].to_vec());
         self.set_biases(biases);
+        if self.is_recurrent() {
+            let recurrent: Matrix<f64> =
+                Matrix::from_vec(self.output_len, self.output_len, genes[b_end..].to_vec());
+            self.set_recurrent_weights(recurrent);
+        }
     }
 }
 
-/// Generate a r/andom vector of given length using a uniform distribution
-/// values in range [-1.0, 1.0]
-fn rand_vec_uniform(length: usize) -> Vec<f64> {
-    let mut rng = thread_rng();
-    (0..length).map(|_| rng.gen::<f64>() * 2.0 - 1.0).collect()
-    // let mut out: Vec<f64> = vec![0.0; length];
-    // for i in 0..length {
-    //     out[i] = ;
-    // }
-    // out
-}
-
 #[cfg(test)]
 mod tests {
     extern crate round;
@@ -200,6 +303,21 @@ mod tests {
         assert_eq!(l.gene_len, 12);
     }
 
+    #[test]
+    fn layer_recurrent_forward() {
+        let mut l = Layer::new_recurrent(1, 1, Activation::Linear);
+        assert_eq!(l.gene_len, 3);
+        l.set_genes(&[1.0, 0.0, 0.5]);
+
+        let input = Matrix::from_vec(1, 1, vec![1.0]);
+        assert_eq!(l.forward(&input)[0], 1.0);
+        assert_eq!(l.forward(&input)[0], 1.5);
+        assert_eq!(l.forward(&input)[0], 1.75);
+
+        l.reset_state();
+        assert_eq!(l.forward(&input)[0], 1.0);
+    }
+
     #[test]
     fn layer_set_genes() {
         let mut l = Layer::new(3, 1, Activation::Relu);
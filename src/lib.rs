@@ -10,21 +10,61 @@ extern crate pretty_env_logger;
 extern crate nalgebra as na;
 
 mod activation;
+#[cfg(feature = "serde")]
+mod ann_format;
+pub mod benchmarks;
+mod champion_policy;
 mod config;
+#[cfg(feature = "serde")]
+mod config_file;
 mod cosyne;
+mod crossover;
+mod error;
+pub mod experiment;
+mod fitness_aggregation;
+mod initializer;
+mod island_model;
 mod layer;
+mod map_elites;
+mod mutation_distribution;
 mod network;
+mod non_finite_policy;
+mod novelty;
+mod observer;
+mod pareto;
 mod permutation_prob_f;
 #[cfg(feature = "plot")]
 mod plot;
 mod population;
+mod restart_strategy;
+mod selection;
+mod stats;
+mod stop_condition;
 
 pub use crate::cosyne::Cosyne;
 pub use activation::Activation;
-pub use config::Config;
+#[cfg(feature = "serde")]
+pub use ann_format::ANN_FORMAT_VERSION;
+pub use champion_policy::ChampionPolicy;
+pub use config::{Config, ConfigBuilder};
+#[cfg(feature = "serde")]
+pub use config_file::{ConfigFile, HiddenLayerSpec, NetworkSpec};
+pub use crossover::Crossover;
+pub use error::CosyneError;
+pub use fitness_aggregation::FitnessAggregation;
+pub use initializer::Initializer;
+pub use island_model::{IslandModel, IslandModelBuilder, IslandReport, MigrationTopology};
+pub use map_elites::{Elite, MapElites};
+pub use mutation_distribution::MutationDistribution;
 pub use network::ANN;
+pub use non_finite_policy::NonFinitePolicy;
+pub use observer::CosyneObserver;
 pub use permutation_prob_f::PermutationProbF;
 pub use population::Population;
+pub use restart_strategy::RestartStrategy;
+pub use selection::Selection;
+pub use stats::GenerationStats;
+pub use stop_condition::{RunReport, StopCondition, StopReason};
 
 pub(crate) use layer::Layer;
 
@@ -32,8 +72,63 @@ pub(crate) use layer::Layer;
 pub(crate) use plot::plot_values;
 
 /// Environment to test the neural network in
+#[cfg(not(feature = "parallel"))]
 pub trait Environment {
     /// Return the fitness of a given neural network in the environment.
     /// Higher values indicate a more fit candidate
     fn evaluate(&self, nn: &mut ANN) -> f64;
 }
+
+/// Environment to test the neural network in.
+/// With the parallel feature enabled, networks are evaluated concurrently,
+/// so the environment has to be shareable across threads
+#[cfg(feature = "parallel")]
+pub trait Environment: Send + Sync {
+    /// Return the fitness of a given neural network in the environment.
+    /// Higher values indicate a more fit candidate
+    fn evaluate(&self, nn: &mut ANN) -> f64;
+}
+
+/// Environment rating a neural network by several objectives,
+/// e.g. task reward and energy use, for use with Cosyne::new_multi_objective
+#[cfg(not(feature = "parallel"))]
+pub trait MultiObjectiveEnvironment {
+    /// Return the objectives of a given neural network in the environment.
+    /// Higher values are better in every objective
+    /// and the number of objectives has to be the same for every network
+    fn evaluate(&self, nn: &mut ANN) -> Vec<f64>;
+}
+
+/// Environment rating a neural network by several objectives,
+/// e.g. task reward and energy use, for use with Cosyne::new_multi_objective.
+/// With the parallel feature enabled, networks are evaluated concurrently,
+/// so the environment has to be shareable across threads
+#[cfg(feature = "parallel")]
+pub trait MultiObjectiveEnvironment: Send + Sync {
+    /// Return the objectives of a given neural network in the environment.
+    /// Higher values are better in every objective
+    /// and the number of objectives has to be the same for every network
+    fn evaluate(&self, nn: &mut ANN) -> Vec<f64>;
+}
+
+/// Environment rating a neural network by its fitness
+/// and describing the behavior it showed, e.g. the final position of a robot,
+/// for use with Cosyne::new_with_behavior
+#[cfg(not(feature = "parallel"))]
+pub trait BehaviorEnvironment {
+    /// Return the fitness and the behavior descriptor of a given neural network.
+    /// The descriptor has to have the same length for every network
+    fn evaluate(&self, nn: &mut ANN) -> (f64, Vec<f64>);
+}
+
+/// Environment rating a neural network by its fitness
+/// and describing the behavior it showed, e.g. the final position of a robot,
+/// for use with Cosyne::new_with_behavior.
+/// With the parallel feature enabled, networks are evaluated concurrently,
+/// so the environment has to be shareable across threads
+#[cfg(feature = "parallel")]
+pub trait BehaviorEnvironment: Send + Sync {
+    /// Return the fitness and the behavior descriptor of a given neural network.
+    /// The descriptor has to have the same length for every network
+    fn evaluate(&self, nn: &mut ANN) -> (f64, Vec<f64>);
+}
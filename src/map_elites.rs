@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use crate::{config::check_count, CosyneError, ANN};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The best network found for a cell of a MapElites archive
+pub struct Elite {
+    /// coordinates of the cell in the grid, one bin index per behavior dimension
+    pub cell: Vec<usize>,
+    /// the network
+    pub network: ANN,
+    /// fitness of the network
+    pub fitness: f64,
+    /// behavior descriptor of the network
+    pub behavior: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Quality-diversity archive (MAP-Elites), which divides the behavior space into a grid
+/// and keeps the fittest network of every cell.
+/// Attach it to a Cosyne with set_map_elites to insert every evaluated network
+pub struct MapElites {
+    // lower bound, upper bound and number of bins of every behavior dimension
+    dimensions: Vec<(f64, f64, usize)>,
+    // elites by the flat index of their cell
+    elites: BTreeMap<usize, Elite>,
+}
+
+impl MapElites {
+    /// Create an empty archive with the lower bound, upper bound and number of bins
+    /// of every behavior dimension.
+    /// Behaviors outside of the bounds are assigned to the outermost cells.
+    /// Returns an error if a dimension has no bins
+    /// or its bounds are not finite with the upper bound greater than the lower bound
+    pub fn new(dimensions: &[(f64, f64, usize)]) -> Result<Self, CosyneError> {
+        for &(low, high, bins) in dimensions {
+            check_count("number of behavior bins", bins)?;
+            if !(low.is_finite() && high.is_finite() && high > low) {
+                return Err(CosyneError::InvalidBounds { low, high });
+            }
+        }
+        Ok(MapElites {
+            dimensions: dimensions.to_vec(),
+            elites: BTreeMap::new(),
+        })
+    }
+
+    /// Return the coordinates of the cell a behavior belongs to.
+    /// Returns an error if the behavior does not have one value per dimension
+    pub fn cell(&self, behavior: &[f64]) -> Result<Vec<usize>, CosyneError> {
+        self.check_behavior(behavior)?;
+        Ok(behavior
+            .iter()
+            .zip(&self.dimensions)
+            .map(|(v, (low, high, bins))| {
+                let bin = ((v - low) / (high - low) * *bins as f64).floor();
+                bin.max(0.0).min(*bins as f64 - 1.0) as usize
+            })
+            .collect())
+    }
+
+    /// Check that the behavior has one value per dimension
+    pub(crate) fn check_behavior(&self, behavior: &[f64]) -> Result<(), CosyneError> {
+        if behavior.len() == self.dimensions.len() {
+            Ok(())
+        } else {
+            Err(CosyneError::BehaviorLengthMismatch {
+                expected: self.dimensions.len(),
+                actual: behavior.len(),
+            })
+        }
+    }
+
+    /// Insert a network if its cell is empty or it is fitter than the elite of its cell.
+    /// Returns true if the network became an elite.
+    /// Networks with a non-finite fitness or behavior are ignored.
+    /// Returns an error if the behavior does not have one value per dimension
+    pub fn insert(
+        &mut self,
+        network: &ANN,
+        fitness: f64,
+        behavior: &[f64],
+    ) -> Result<bool, CosyneError> {
+        let cell = self.cell(behavior)?;
+        if !fitness.is_finite() || !behavior.iter().all(|v| v.is_finite()) {
+            return Ok(false);
+        }
+        let index = self.flat_index(&cell);
+        if let Some(elite) = self.elites.get(&index) {
+            if elite.fitness >= fitness {
+                return Ok(false);
+            }
+        }
+        self.elites.insert(
+            index,
+            Elite {
+                cell,
+                network: network.clone(),
+                fitness,
+                behavior: behavior.to_vec(),
+            },
+        );
+        Ok(true)
+    }
+
+    /// Return the elite of a cell, if the cell is filled
+    pub fn get(&self, cell: &[usize]) -> Option<&Elite> {
+        self.elites.get(&self.flat_index(cell))
+    }
+
+    /// Return all elites, ordered by their cell
+    pub fn elites(&self) -> impl Iterator<Item = &Elite> {
+        self.elites.values()
+    }
+
+    /// Return the fittest elite, if any
+    pub fn best(&self) -> Option<&Elite> {
+        self.elites().max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+    }
+
+    /// Return the number of filled cells
+    pub fn filled_cells(&self) -> usize {
+        self.elites.len()
+    }
+
+    /// Return the total number of cells of the grid
+    pub fn num_cells(&self) -> usize {
+        self.dimensions.iter().map(|(_, _, bins)| bins).product()
+    }
+
+    /// Return the fraction of filled cells
+    pub fn coverage(&self) -> f64 {
+        self.filled_cells() as f64 / self.num_cells() as f64
+    }
+
+    /// Return the QD-score, which is the sum of the fitness of all elites.
+    /// It only rewards filling cells if fitness values are not negative
+    pub fn qd_score(&self) -> f64 {
+        self.elites().map(|e| e.fitness).sum()
+    }
+
+    /// Return the index of a cell in row major order
+    fn flat_index(&self, cell: &[usize]) -> usize {
+        cell.iter()
+            .zip(&self.dimensions)
+            .fold(0, |index, (c, (_, _, bins))| index * bins + c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Activation;
+
+    #[test]
+    fn cells() {
+        let archive = MapElites::new(&[(0.0, 1.0, 4), (-1.0, 1.0, 2)]).unwrap();
+        assert_eq!(archive.num_cells(), 8);
+        assert_eq!(archive.cell(&[0.3, -0.5]), Ok(vec![1, 0]));
+        assert_eq!(archive.cell(&[1.0, 0.5]), Ok(vec![3, 1]));
+        // out of bounds behaviors are clamped to the outermost cells
+        assert_eq!(archive.cell(&[-3.0, 5.0]), Ok(vec![0, 1]));
+        assert_eq!(archive.flat_index(&[3, 1]), 7);
+        assert_eq!(
+            archive.cell(&[0.5]),
+            Err(CosyneError::BehaviorLengthMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_dimensions() {
+        assert_eq!(
+            MapElites::new(&[(0.0, 1.0, 0)]).unwrap_err(),
+            CosyneError::MustBePositive {
+                name: "number of behavior bins"
+            }
+        );
+        assert_eq!(
+            MapElites::new(&[(1.0, 1.0, 4)]).unwrap_err(),
+            CosyneError::InvalidBounds {
+                low: 1.0,
+                high: 1.0
+            }
+        );
+        assert!(MapElites::new(&[(0.0, f64::INFINITY, 4)]).is_err());
+    }
+
+    #[test]
+    fn keeps_fittest() {
+        let mut archive = MapElites::new(&[(0.0, 1.0, 2)]).unwrap();
+        let nn = ANN::new(1, 1, Activation::Linear);
+        assert_eq!(archive.insert(&nn, 1.0, &[0.1]), Ok(true));
+        assert_eq!(archive.insert(&nn, 0.5, &[0.2]), Ok(false));
+        assert_eq!(archive.insert(&nn, 2.0, &[0.3]), Ok(true));
+        assert_eq!(archive.insert(&nn, 3.0, &[0.9]), Ok(true));
+        assert_eq!(archive.insert(&nn, f64::NAN, &[0.9]), Ok(false));
+        assert_eq!(archive.insert(&nn, 4.0, &[f64::NAN]), Ok(false));
+        assert!(archive.insert(&nn, 4.0, &[0.1, 0.2]).is_err());
+
+        assert_eq!(archive.filled_cells(), 2);
+        assert_eq!(archive.coverage(), 1.0);
+        assert_eq!(archive.qd_score(), 5.0);
+        assert_eq!(archive.get(&[0]).unwrap().behavior, vec![0.3]);
+        assert_eq!(archive.best().unwrap().fitness, 3.0);
+    }
+}
@@ -0,0 +1,77 @@
+use rand::Rng;
+use rand_distr::{Cauchy, Normal, Open01};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the zero centered distributions that mutations are drawn from.
+/// Samples are scaled by the mutation strength of the Config
+pub enum MutationDistribution {
+    /// Normal distribution with the given standard deviation
+    Gaussian(f64),
+    /// Heavy tailed Cauchy distribution with the given scale
+    Cauchy(f64),
+    /// Laplace (double exponential) distribution with the given scale
+    Laplace(f64),
+    /// Uniform distribution in the range [-w, w] for the given w
+    Uniform(f64),
+}
+
+impl MutationDistribution {
+    /// Draw a single sample from the distribution
+    pub(crate) fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        match self {
+            MutationDistribution::Gaussian(sigma) => rng.sample(Normal::new(0.0, *sigma).unwrap()),
+            MutationDistribution::Cauchy(scale) => rng.sample(Cauchy::new(0.0, *scale).unwrap()),
+            MutationDistribution::Laplace(scale) => {
+                // inverse transform sampling, the open interval keeps ln away from 0
+                let u: f64 = rng.sample::<f64, _>(Open01) - 0.5;
+                -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+            }
+            MutationDistribution::Uniform(w) => (rng.gen::<f64>() * 2.0 - 1.0) * w,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    fn samples(d: MutationDistribution) -> Vec<f64> {
+        let mut rng = thread_rng();
+        (0..10_000).map(|_| d.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn mutation_distribution_uniform() {
+        let s = samples(MutationDistribution::Uniform(0.5));
+        assert!(s.iter().all(|v| (-0.5..=0.5).contains(v)));
+    }
+
+    #[test]
+    fn mutation_distribution_spread() {
+        // mean absolute deviation is sigma * sqrt(2 / pi) for Gaussian and the scale for Laplace
+        let s = samples(MutationDistribution::Gaussian(2.0));
+        let mad = s.iter().map(|v| v.abs()).sum::<f64>() / s.len() as f64;
+        assert!((mad - 1.596).abs() < 0.1);
+
+        let s = samples(MutationDistribution::Laplace(2.0));
+        let mad = s.iter().map(|v| v.abs()).sum::<f64>() / s.len() as f64;
+        assert!((mad - 2.0).abs() < 0.15);
+        assert!(s.iter().all(|v| v.is_finite()));
+
+        // the lowest possible random number must not produce an infinite weight
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        assert!(MutationDistribution::Laplace(2.0)
+            .sample(&mut rng)
+            .is_finite());
+    }
+
+    #[test]
+    fn mutation_distribution_cauchy() {
+        // half of the samples lie within one scale of the center
+        let s = samples(MutationDistribution::Cauchy(1.0));
+        let inside = s.iter().filter(|v| v.abs() < 1.0).count() as f64 / s.len() as f64;
+        assert!((inside - 0.5).abs() < 0.05);
+    }
+}
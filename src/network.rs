@@ -1,8 +1,10 @@
 use na::DMatrix as Matrix;
+use rand::Rng;
 
-use crate::{Activation, Layer};
+use crate::{Activation, CosyneError, Initializer, Layer};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Artificial Neural Network
 pub struct ANN {
     num_inputs: usize,
@@ -16,29 +18,45 @@ impl ANN {
     /// with a given number of inputs and outputs and an activation function
     pub fn new(num_inputs: usize, num_outputs: usize, act_func: Activation) -> ANN {
         let layers = vec![Layer::new(num_inputs, num_outputs, act_func)];
-        let num_genes = layers.iter().map(|l| l.num_genes()).sum();
 
-        ANN {
-            num_inputs,
-            num_outputs,
-            layers,
-            num_genes,
-        }
+        ANN::from_layers(num_inputs, num_outputs, layers)
     }
 
     /// Add a new hidden layer with a given neuron count and activation function.
     /// This modifies the previous and following layer to match io in each layer
     pub fn add_layer(&mut self, neuron_count: usize, act: Activation) {
+        self.insert_hidden_layer(neuron_count, act, false);
+    }
+
+    /// Add a new self-recurrent hidden layer with a given neuron count (Elman network).
+    /// The hidden neurons receive their own previous output through recurrent weights,
+    /// which are part of the genes and evolved like all other weights.
+    /// Use reset_state at episode boundaries
+    pub fn add_recurrent_layer(&mut self, neuron_count: usize, act: Activation) {
+        self.insert_hidden_layer(neuron_count, act, true);
+    }
+
+    /// Insert a hidden layer before the output layer
+    fn insert_hidden_layer(&mut self, neuron_count: usize, act: Activation, recurrent: bool) {
         let last_layer_idx = self.layers.len() - 1;
+        let weight_init = self.layers[last_layer_idx].weight_init;
+        let bias_init = self.layers[last_layer_idx].bias_init;
 
         // set new layer as output layer
-        self.layers
-            .push(Layer::new(neuron_count, self.num_outputs, act));
+        let mut output_layer = Layer::new(neuron_count, self.num_outputs, act);
+        output_layer.set_initializer(weight_init, bias_init);
+        self.layers.push(output_layer);
 
         // modify previous layer output_len to match neuron_count of new layer
         let old_input_len = self.layers[last_layer_idx].input_len;
         let old_activation = self.layers[last_layer_idx].activation;
-        self.layers[last_layer_idx] = Layer::new(old_input_len, neuron_count, old_activation);
+        let mut hidden_layer = if recurrent {
+            Layer::new_recurrent(old_input_len, neuron_count, old_activation)
+        } else {
+            Layer::new(old_input_len, neuron_count, old_activation)
+        };
+        hidden_layer.set_initializer(weight_init, bias_init);
+        self.layers[last_layer_idx] = hidden_layer;
 
         // re-compute num_genes
         self.num_genes = self.layers.iter().map(|l| l.num_genes()).sum();
@@ -52,7 +70,35 @@ impl ANN {
             prev_output = self.layers[l].forward(&prev_output);
         }
 
-        return prev_output.as_slice().into();
+        prev_output.as_slice().into()
+    }
+
+    /// Clear the state of all recurrent layers, e.g. at the start of a new episode
+    pub fn reset_state(&mut self) {
+        for l in &mut self.layers {
+            l.reset_state();
+        }
+    }
+
+    /// Create a network from already connected layers
+    pub(crate) fn from_layers(num_inputs: usize, num_outputs: usize, layers: Vec<Layer>) -> ANN {
+        let num_genes = layers.iter().map(|l| l.num_genes()).sum();
+        ANN {
+            num_inputs,
+            num_outputs,
+            layers,
+            num_genes,
+        }
+    }
+
+    /// Return the number of inputs of the network
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// Return the number of outputs of the network
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
     }
 
     /// Return the number of genes in the network
@@ -70,8 +116,14 @@ impl ANN {
     }
 
     /// update the network weights and biases with new genes
-    pub(crate) fn set_genes(&mut self, genes: &Vec<f64>) {
-        assert_eq!(genes.len(), self.num_genes());
+    /// returns an error if genes.len() != self.num_genes()
+    pub fn set_genes(&mut self, genes: &[f64]) -> Result<(), CosyneError> {
+        if genes.len() != self.num_genes() {
+            return Err(CosyneError::GeneCountMismatch {
+                expected: self.num_genes(),
+                actual: genes.len(),
+            });
+        }
 
         let mut start: usize = 0;
         for l in &mut self.layers {
@@ -79,21 +131,76 @@ impl ANN {
             l.set_genes(&genes[start..end]);
             start += l.gene_len;
         }
+
+        Ok(())
     }
 
     /// randomize returns a new randomized instance of ANN
-    pub(crate) fn randomize(&self) -> ANN {
-        let mut layers: Vec<Layer> = Vec::new();
-        for l in &self.layers {
-            layers.push(Layer::new(l.input_len, l.output_len, l.activation))
+    pub(crate) fn randomize<R: Rng>(&self, rng: &mut R) -> ANN {
+        let mut nn = self.clone();
+        for l in &mut nn.layers {
+            l.randomize(rng);
         }
-        let num_genes = layers.iter().map(|l| l.num_genes()).sum();
-        ANN {
-            num_inputs: self.num_inputs,
-            num_outputs: self.num_outputs,
-            layers,
-            num_genes,
+        nn.reset_state();
+
+        nn
+    }
+
+    /// Set the initializers for the weights and biases of all layers
+    /// and re-initialize the network with them.
+    /// They are used whenever the network is randomized, e.g. for the initial Population.
+    /// Layers added afterwards use the initializers of the current output layer.
+    /// Returns an error if an initializer has invalid bounds or standard deviation
+    pub fn set_initializer(
+        &mut self,
+        weights: Initializer,
+        biases: Initializer,
+    ) -> Result<(), CosyneError> {
+        weights.validate()?;
+        biases.validate()?;
+        for l in &mut self.layers {
+            l.set_initializer(weights, biases);
+        }
+        Ok(())
+    }
+
+    /// Set the initializers for the weights and biases of a single layer
+    /// and re-initialize the layer with them.
+    /// Layer 0 is connected to the inputs, the last layer produces the outputs.
+    /// Returns an error if the layer does not exist
+    /// or an initializer has invalid bounds or standard deviation
+    pub fn set_layer_initializer(
+        &mut self,
+        layer: usize,
+        weights: Initializer,
+        biases: Initializer,
+    ) -> Result<(), CosyneError> {
+        let num_layers = self.layers.len();
+        let l = self
+            .layers
+            .get_mut(layer)
+            .ok_or(CosyneError::LayerOutOfRange { layer, num_layers })?;
+        weights.validate()?;
+        biases.validate()?;
+        l.set_initializer(weights, biases);
+        Ok(())
+    }
+
+    /// Check the initializers of all layers, e.g. of a deserialized network
+    pub(crate) fn validate_initializers(&self) -> Result<(), CosyneError> {
+        for l in &self.layers {
+            l.weight_init.validate()?;
+            l.bias_init.validate()?;
         }
+        Ok(())
+    }
+
+    /// Return the initializer of every gene, in the same order as genes
+    pub(crate) fn gene_initializers(&self) -> Vec<Initializer> {
+        self.layers
+            .iter()
+            .flat_map(|l| l.gene_initializers())
+            .collect()
     }
 }
 
@@ -177,16 +284,74 @@ mod tests {
         assert_eq!(genes.len(), 16);
     }
 
+    #[test]
+    fn recurrent_network() {
+        let mut nn = ANN::new(2, 1, Activation::Linear);
+        nn.add_recurrent_layer(3, Activation::Linear);
+
+        assert!(nn.layers[0].is_recurrent());
+        assert!(!nn.layers[1].is_recurrent());
+        assert_eq!(nn.num_genes(), 2 * 3 + 3 + 3 * 3 + 3 + 1);
+        assert_eq!(nn.genes().len(), nn.num_genes());
+
+        nn.set_genes(&vec![0.1; nn.num_genes()]).unwrap();
+        let first = nn.forward(vec![1.0, 1.0]);
+        let second = nn.forward(vec![1.0, 1.0]);
+        assert_ne!(first, second);
+
+        nn.reset_state();
+        assert_eq!(nn.forward(vec![1.0, 1.0]), first);
+    }
+
+    #[test]
+    fn network_initializer() {
+        let mut nn = ANN::new(3, 1, Activation::Relu);
+        nn.set_initializer(Initializer::Uniform(0.5, 1.0), Initializer::Zeros)
+            .unwrap();
+        nn.add_layer(4, Activation::Relu);
+        nn.set_layer_initializer(1, Initializer::Zeros, Initializer::Zeros)
+            .unwrap();
+        assert_eq!(
+            nn.set_layer_initializer(2, Initializer::Zeros, Initializer::Zeros),
+            Err(CosyneError::LayerOutOfRange {
+                layer: 2,
+                num_layers: 2
+            })
+        );
+        assert!(nn
+            .set_initializer(Initializer::Gaussian(0.0, -1.0), Initializer::Zeros)
+            .is_err());
+
+        let inits = nn.gene_initializers();
+        assert_eq!(inits.len(), nn.num_genes());
+        assert!(inits[..12]
+            .iter()
+            .all(|i| *i == Initializer::Uniform(0.5, 1.0)));
+        assert!(inits[12..].iter().all(|i| *i == Initializer::Zeros));
+
+        let genes = nn.randomize(&mut rand::thread_rng()).genes();
+        assert!(genes[..12].iter().all(|v| (0.5..=1.0).contains(v)));
+        assert!(genes[12..].iter().all(|v| *v == 0.0));
+    }
+
     #[test]
     fn network_set_genes() {
         let mut nn = ANN::new(3, 1, Activation::Relu);
         let genes = vec![1.0; 4];
-        nn.set_genes(&genes);
+        nn.set_genes(&genes).unwrap();
 
         nn.add_layer(3, Activation::Relu);
 
         let genes = vec![1.0; 16];
-        nn.set_genes(&genes);
+        nn.set_genes(&genes).unwrap();
         assert_eq!(nn.genes(), genes);
+
+        assert_eq!(
+            nn.set_genes(&[1.0; 4]),
+            Err(CosyneError::GeneCountMismatch {
+                expected: 16,
+                actual: 4
+            })
+        );
     }
 }
@@ -0,0 +1,26 @@
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the ways of handling NaN or infinite fitness values returned by an Environment,
+/// before they enter the Population
+pub enum NonFinitePolicy {
+    /// Replace the fitness with the lowest finite fitness of the generation,
+    /// or f64::MIN if no network of the generation has a finite fitness.
+    /// Networks left at f64::MIN are not included in the GenerationStats
+    #[default]
+    TreatAsWorst,
+    /// Evaluate the network again up to the given number of times,
+    /// then treat it as the worst network if its fitness is still not finite
+    Reevaluate(usize),
+    /// Stop evolving and return CosyneError::NonFiniteFitness
+    Abort,
+}
+
+impl NonFinitePolicy {
+    /// Return the maximum number of evaluations of a single network per generation
+    pub(crate) fn max_attempts(&self) -> usize {
+        match self {
+            NonFinitePolicy::Reevaluate(n) => n + 1,
+            NonFinitePolicy::TreatAsWorst | NonFinitePolicy::Abort => 1,
+        }
+    }
+}
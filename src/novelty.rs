@@ -0,0 +1,100 @@
+//! Novelty search: behaviors are rated by their mean distance to the k nearest behaviors
+//! of the current population and of an archive of earlier novel behaviors
+
+/// Return the novelty of every behavior, which is the mean euclidean distance
+/// to its k nearest neighbours among the other behaviors and the archive.
+/// Behaviors with non-finite values have a novelty of 0 and are nobody's neighbour
+pub(crate) fn novelty(behaviors: &[Vec<f64>], archive: &[Vec<f64>], k: usize) -> Vec<f64> {
+    let finite = |b: &Vec<f64>| b.iter().all(|v| v.is_finite());
+    behaviors
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            if !finite(b) {
+                return 0.0;
+            }
+            let mut distances: Vec<f64> = behaviors
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| *j != i && finite(other))
+                .map(|(_, other)| other)
+                .chain(archive)
+                .map(|other| distance(b, other))
+                .collect();
+            distances.sort_by(f64::total_cmp);
+            distances.truncate(k);
+            if distances.is_empty() {
+                0.0
+            } else {
+                distances.iter().sum::<f64>() / distances.len() as f64
+            }
+        })
+        .collect()
+}
+
+/// Return the index of the most novel behavior, which is added to the archive
+pub(crate) fn most_novel(novelty: &[f64]) -> Option<usize> {
+    (0..novelty.len()).max_by(|a, b| novelty[*a].total_cmp(&novelty[*b]))
+}
+
+/// Blend fitness and novelty after normalizing both to [0, 1],
+/// weight is the share of the novelty
+pub(crate) fn blend(fits: &[f64], novelty: &[f64], weight: f64) -> Vec<f64> {
+    let fits = normalize(fits);
+    let novelty = normalize(novelty);
+    fits.iter()
+        .zip(novelty)
+        .map(|(f, n)| (1.0 - weight) * f + weight * n)
+        .collect()
+}
+
+/// Scale values linearly to [0, 1], all equal values become 0
+fn normalize(values: &[f64]) -> Vec<f64> {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Euclidean distance between two behaviors
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_neighbours() {
+        let behaviors = vec![vec![0.0], vec![1.0], vec![3.0], vec![f64::NAN]];
+        let n = novelty(&behaviors, &[], 1);
+        assert_eq!(n, vec![1.0, 1.0, 2.0, 0.0]);
+
+        let n = novelty(&behaviors, &[vec![10.0]], 2);
+        assert_eq!(n, vec![2.0, 1.5, 2.5, 0.0]);
+        assert_eq!(most_novel(&n), Some(2));
+    }
+
+    #[test]
+    fn blended_scores() {
+        let fits = vec![0.0, 5.0, 10.0];
+        let novelty = vec![4.0, 2.0, 0.0];
+        assert_eq!(blend(&fits, &novelty, 0.0), vec![0.0, 0.5, 1.0]);
+        assert_eq!(blend(&fits, &novelty, 1.0), vec![1.0, 0.5, 0.0]);
+        assert_eq!(blend(&fits, &novelty, 0.5), vec![0.5, 0.5, 0.5]);
+        assert_eq!(blend(&[1.0, 1.0], &[1.0, 1.0], 0.5), vec![0.0, 0.0]);
+    }
+}
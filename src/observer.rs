@@ -0,0 +1,24 @@
+use crate::{GenerationStats, ANN};
+
+/// Hooks into the evolutionary loop of Cosyne, registered with Cosyne::add_observer.
+/// All methods have empty default implementations,
+/// so only the hooks of interest need to be implemented
+pub trait CosyneObserver {
+    /// Called at the start of every call to Cosyne::evolve
+    fn on_generation_start(&mut self, _generation: usize) {}
+
+    /// Called for every network of the population after it has been evaluated,
+    /// in index order even with the parallel feature
+    fn on_evaluated(&mut self, _j: usize, _fitness: f64) {}
+
+    /// Called whenever a network becomes the new champion
+    fn on_new_champion(&mut self, _champion: &ANN, _fitness: f64) {}
+
+    /// Called at the end of every generation with its statistics
+    fn on_generation_end(&mut self, _stats: &GenerationStats) {}
+
+    /// Return true to end Cosyne::run after the current generation
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,173 @@
+//! Non-dominated sorting and crowding distance as used by NSGA-II.
+//! All objectives are maximized
+
+/// Return true if a is at least as good as b in every objective and better in one
+pub(crate) fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Sort the indices of the objective vectors into fronts,
+/// where the first front is not dominated by any other vector
+/// and each following front is only dominated by earlier fronts
+pub(crate) fn non_dominated_sort(objectives: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let n = objectives.len();
+    // number of vectors dominating each vector and the vectors each one dominates
+    let mut dominated_by: Vec<usize> = vec![0; n];
+    let mut dominating: Vec<Vec<usize>> = vec![vec![]; n];
+    for i in 0..n {
+        for j in 0..n {
+            if dominates(&objectives[i], &objectives[j]) {
+                dominating[i].push(j);
+            } else if dominates(&objectives[j], &objectives[i]) {
+                dominated_by[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts: Vec<Vec<usize>> = vec![];
+    let mut front: Vec<usize> = (0..n).filter(|i| dominated_by[*i] == 0).collect();
+    while !front.is_empty() {
+        let mut next: Vec<usize> = vec![];
+        for i in &front {
+            for j in &dominating[*i] {
+                dominated_by[*j] -= 1;
+                if dominated_by[*j] == 0 {
+                    next.push(*j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+/// Return the crowding distance of every member of a front, in the order of the front.
+/// Members at the boundary of any objective get an infinite distance
+pub(crate) fn crowding_distance(objectives: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let mut distance: Vec<f64> = vec![0.0; front.len()];
+    let num_objectives = front.first().map_or(0, |i| objectives[*i].len());
+    // values of each objective for every member of the front
+    let columns = (0..num_objectives).map(|o| {
+        front
+            .iter()
+            .map(|i| objectives[*i][o])
+            .collect::<Vec<f64>>()
+    });
+    for values in columns {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+        let min = values[order[0]];
+        let max = values[order[order.len() - 1]];
+        distance[order[0]] = f64::INFINITY;
+        distance[order[order.len() - 1]] = f64::INFINITY;
+        if max == min {
+            continue;
+        }
+        for k in 1..order.len() - 1 {
+            distance[order[k]] += (values[order[k + 1]] - values[order[k - 1]]) / (max - min);
+        }
+    }
+
+    distance
+}
+
+/// Score every objective vector by its front and crowding distance,
+/// so that higher scores are better:
+/// -rank + 0.5 * d / (1 + d), with rank the index of the front and d the crowding distance.
+/// Any member of a front scores higher than all members of the following fronts
+pub(crate) fn scores(objectives: &[Vec<f64>]) -> Vec<f64> {
+    let mut scores: Vec<f64> = vec![0.0; objectives.len()];
+    for (rank, front) in non_dominated_sort(objectives).iter().enumerate() {
+        for (i, d) in front.iter().zip(crowding_distance(objectives, front)) {
+            let bonus = if d.is_infinite() {
+                0.5
+            } else {
+                0.5 * d / (1.0 + d)
+            };
+            scores[*i] = -(rank as f64) + bonus;
+        }
+    }
+
+    scores
+}
+
+/// Return the indices of the non-dominated vectors, keeping at most max_len of them.
+/// If there are too many, the most crowded ones are dropped
+pub(crate) fn pareto_front(objectives: &[Vec<f64>], max_len: usize) -> Vec<usize> {
+    let mut front: Vec<usize> = vec![];
+    for i in non_dominated_sort(objectives)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+    {
+        // drop duplicates, which would otherwise fill up the front
+        if !front.iter().any(|f| objectives[*f] == objectives[i]) {
+            front.push(i);
+        }
+    }
+    while front.len() > max_len {
+        let distance = crowding_distance(objectives, &front);
+        let most_crowded = (0..front.len())
+            .min_by(|a, b| distance[*a].total_cmp(&distance[*b]))
+            .unwrap();
+        front.remove(most_crowded);
+    }
+
+    front
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objectives() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 5.0],
+            vec![2.0, 4.0],
+            vec![1.0, 1.0],
+            vec![3.0, 3.0],
+            vec![5.0, 1.0],
+            vec![2.0, 2.0],
+        ]
+    }
+
+    #[test]
+    fn domination() {
+        assert!(dominates(&[2.0, 2.0], &[1.0, 2.0]));
+        assert!(!dominates(&[2.0, 2.0], &[2.0, 2.0]));
+        assert!(!dominates(&[3.0, 1.0], &[1.0, 3.0]));
+    }
+
+    #[test]
+    fn sort_into_fronts() {
+        let fronts = non_dominated_sort(&objectives());
+        assert_eq!(fronts, vec![vec![0, 1, 3, 4], vec![5], vec![2]]);
+    }
+
+    #[test]
+    fn crowding() {
+        let objs = objectives();
+        let d = crowding_distance(&objs, &[0, 1, 3, 4]);
+        assert!(d[0].is_infinite() && d[3].is_infinite());
+        // neighbours of 1 are 0 and 3, neighbours of 3 are 1 and 4
+        assert_eq!(d[1], 2.0 / 4.0 + 2.0 / 4.0);
+        assert_eq!(d[2], 3.0 / 4.0 + 3.0 / 4.0);
+    }
+
+    #[test]
+    fn scores_order_fronts() {
+        let s = scores(&objectives());
+        assert!(s[0] > s[5] && s[1] > s[5] && s[5] > s[2]);
+        // boundary members are preferred over crowded ones
+        assert!(s[4] > s[3] && s[3] > s[1]);
+    }
+
+    #[test]
+    fn truncated_front() {
+        let objs = objectives();
+        let front = pareto_front(&objs, 3);
+        assert_eq!(front, vec![0, 3, 4]);
+    }
+}
@@ -1,4 +1,7 @@
-#[derive(Debug, Clone, Copy)]
+use crate::population::DEFAULT_FIT;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Enumerate the probability functions used for marking weights for permutation
 pub enum PermutationProbF {
     /// Each weight has the same probability of being marked for permutation
@@ -6,12 +9,28 @@ pub enum PermutationProbF {
     /// Each weight has a probability of being permuted relative to it's peers in sub-population
     /// not quite the same as in paper
     Relative,
+    /// The probability function of the paper:
+    /// 1 - n_th_root((f(x) - f_min) / (f_max - f_min)),
+    /// where n is the number of sub-populations (genes) of the network.
+    /// The fittest weight is never permuted, the least fit one always.
+    /// Offspring weights that were not evaluated yet are always permuted
+    /// and do not count towards f_min and f_max
+    FitnessProportional,
+    /// Probability decreases linearly with the rank of the weight in its sub-population:
+    /// 1 - rank / (m - 1), where the least fit weight has rank 0
+    /// and m is the size of the sub-population
+    Rank,
 }
 
 impl PermutationProbF {
     /// Return the probability that a weight is marked for permutation
-    /// given the sub-population fitnesses
-    pub(crate) fn get_probability(&self, subpopulation_fits: &Vec<f64>, weight_fit: f64) -> f64 {
+    /// given the sub-population fitnesses and the number of sub-populations
+    pub(crate) fn get_probability(
+        &self,
+        subpopulation_fits: &[f64],
+        weight_fit: f64,
+        num_sub_populations: usize,
+    ) -> f64 {
         match self {
             PermutationProbF::Uniform(p) => *p,
             PermutationProbF::Relative => {
@@ -27,6 +46,35 @@ impl PermutationProbF {
 
                 1.0 - scale(min_fit, max_fit, 0.0, 1.0, weight_fit)
             }
+            PermutationProbF::FitnessProportional => {
+                let evaluated = |f: f64| f.is_finite() && f != DEFAULT_FIT;
+                if !evaluated(weight_fit) {
+                    return 1.0;
+                }
+                // ignore offspring that were not evaluated yet and weights without a finite
+                // fitness, so they cannot distort the range
+                let finite = subpopulation_fits.iter().filter(|f| evaluated(**f));
+                let min_fit: f64 = finite.clone().fold(weight_fit, |a, b| a.min(*b));
+                let max_fit: f64 = finite.fold(weight_fit, |a, b| a.max(*b));
+                if min_fit == max_fit {
+                    // all weights are equally fit
+                    return 1.0;
+                }
+
+                let normalized = scale(min_fit, max_fit, 0.0, 1.0, weight_fit);
+                1.0 - normalized.powf(1.0 / num_sub_populations.max(1) as f64)
+            }
+            PermutationProbF::Rank => {
+                if subpopulation_fits.len() < 2 {
+                    return 1.0;
+                }
+                let rank = subpopulation_fits
+                    .iter()
+                    .filter(|f| **f < weight_fit)
+                    .count();
+
+                1.0 - rank as f64 / (subpopulation_fits.len() - 1) as f64
+            }
         }
     }
 }
@@ -44,12 +92,49 @@ mod tests {
     #[test]
     fn permutation_prob_f() {
         let ppf = PermutationProbF::Uniform(1.0);
-        assert_eq!(ppf.get_probability(&vec![], 0.5), 1.0);
+        assert_eq!(ppf.get_probability(&[], 0.5, 1), 1.0);
 
         let ppf = PermutationProbF::Relative;
         let spf: Vec<f64> = vec![0.1, 0.2, 0.5, -0.1, -0.2];
         let weight_fit: f64 = spf[0];
-        let prob: f64 = ppf.get_probability(&spf, weight_fit);
+        let prob: f64 = ppf.get_probability(&spf, weight_fit, 1);
         assert_eq!(round(prob, 3), 0.571);
     }
+
+    #[test]
+    fn permutation_prob_f_fitness_proportional() {
+        let ppf = PermutationProbF::FitnessProportional;
+        let spf: Vec<f64> = vec![0.1, 0.2, 0.5, -0.1, -0.2];
+
+        // with a single sub-population this reduces to the relative probability
+        assert_eq!(round(ppf.get_probability(&spf, 0.1, 1), 3), 0.571);
+        // 1 - sqrt(0.3 / 0.7)
+        assert_eq!(round(ppf.get_probability(&spf, 0.1, 2), 3), 0.345);
+        // 1 - cbrt(0.4 / 0.7)
+        assert_eq!(round(ppf.get_probability(&spf, 0.2, 3), 3), 0.17);
+        assert_eq!(ppf.get_probability(&spf, 0.5, 3), 0.0);
+        assert_eq!(ppf.get_probability(&spf, -0.2, 3), 1.0);
+
+        // equal fitnesses
+        assert_eq!(ppf.get_probability(&[0.3, 0.3], 0.3, 2), 1.0);
+        // offspring inserted by replace_and_permute have not been evaluated yet
+        let spf: Vec<f64> = vec![0.0, 1.0, DEFAULT_FIT];
+        assert_eq!(round(ppf.get_probability(&spf, 0.25, 2), 3), 0.5);
+        assert_eq!(ppf.get_probability(&spf, DEFAULT_FIT, 2), 1.0);
+    }
+
+    #[test]
+    fn permutation_prob_f_rank() {
+        let ppf = PermutationProbF::Rank;
+        let spf: Vec<f64> = vec![0.1, 0.2, 0.5, -0.1, -0.2];
+
+        assert_eq!(ppf.get_probability(&spf, -0.2, 5), 1.0);
+        assert_eq!(ppf.get_probability(&spf, -0.1, 5), 0.75);
+        assert_eq!(ppf.get_probability(&spf, 0.1, 5), 0.5);
+        assert_eq!(ppf.get_probability(&spf, 0.2, 5), 0.25);
+        assert_eq!(ppf.get_probability(&spf, 0.5, 5), 0.0);
+
+        // ties share the lower rank
+        assert_eq!(ppf.get_probability(&[1.0, 1.0, 2.0], 1.0, 1), 1.0);
+    }
 }
@@ -2,24 +2,49 @@ use failure::Error;
 use plotters::prelude::*;
 
 // prepare_vec returns a 2d vector suitable for plotting and also min, max values of input vector
-pub(crate) fn prepare_vec(vals: &Vec<f64>) -> (Vec<(f64, f64)>, f64, f64) {
-    let mut out = vec![(0.0, 0.0); vals.len()];
-    let mut min = vals[0];
-    let mut max = vals[0];
+// values that are not finite are skipped
+pub(crate) fn prepare_vec(vals: &[f64]) -> (Vec<(f64, f64)>, f64, f64) {
+    let mut out = Vec::with_capacity(vals.len());
+    let mut min = f64::MAX;
+    let mut max = f64::MIN;
 
     for (i, v) in vals.iter().enumerate() {
-        out[i] = (i as f64, *v);
+        if !v.is_finite() {
+            continue;
+        }
+        out.push((i as f64, *v));
         if *v > max {
             max = *v
-        } else if *v < min {
+        }
+        if *v < min {
             min = *v
         }
     }
-    return (out, min, max);
+    (out, min, max)
 }
 
-pub fn plot_values(vals: &Vec<f64>, filename: &str, resolution: (u32, u32)) -> Result<(), Error> {
-    let (vec2d, mut min, mut max) = prepare_vec(vals);
+/// Plot several named series of values into one chart
+pub fn plot_values(
+    series: &[(&str, Vec<f64>)],
+    filename: &str,
+    resolution: (u32, u32),
+) -> Result<(), Error> {
+    let prepared: Vec<(&str, Vec<(f64, f64)>)> = series
+        .iter()
+        .map(|(name, vals)| (*name, prepare_vec(vals).0))
+        .collect();
+    let (_, mut min, mut max) = prepare_vec(
+        &series
+            .iter()
+            .flat_map(|(_, vals)| vals.iter().copied())
+            .collect::<Vec<f64>>(),
+    );
+    let len = series.iter().map(|(_, vals)| vals.len()).max().unwrap_or(0);
+    if min > max {
+        // nothing to plot
+        min = 0.0;
+        max = 1.0;
+    }
     if min == max {
         // so that plotting does not get stuck
         min -= (min * 0.05).abs();
@@ -34,7 +59,7 @@ pub fn plot_values(vals: &Vec<f64>, filename: &str, resolution: (u32, u32)) -> R
         .margin(40)
         .x_label_area_size(30)
         .y_label_area_size(30)
-        .build_cartesian_2d(0f64..vec2d.len() as f64, min..max)?;
+        .build_cartesian_2d(0f64..len as f64, min..max)?;
 
     chart
         .configure_mesh()
@@ -44,14 +69,18 @@ pub fn plot_values(vals: &Vec<f64>, filename: &str, resolution: (u32, u32)) -> R
         .y_label_formatter(&|v| format!("{:.2}", v))
         .draw()?;
 
-    chart
-        .draw_series(LineSeries::new(vec2d, &BLACK))?
-        .label(filename);
+    for (idx, (name, vec2d)) in prepared.into_iter().enumerate() {
+        let color = Palette99::pick(idx).to_rgba();
+        chart
+            .draw_series(LineSeries::new(vec2d, &color))?
+            .label(name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     Ok(())
@@ -1,10 +1,13 @@
-use crate::{Config, ANN};
-use rand::{thread_rng, Rng};
-use rand_distr::{Distribution, Normal, Uniform};
+use crate::{pareto, stats::mean_std_dev, Config, CosyneError, Initializer, ANN};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rand_distr::Uniform;
 
-pub(crate) const DEFAULT_FIT: f64 = std::f64::MIN;
+pub(crate) const DEFAULT_FIT: f64 = f64::MIN;
 
 /// stores all sub-populations and their fitness values
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Population {
     config: Config,
     network_topology: ANN,
@@ -19,23 +22,26 @@ pub struct Population {
     // so for each weight (or bias) in the nn topology there are m different weight (or bias) variations
     m: usize,
     current_generation: usize,
+    // single source of randomness for initialization and all genetic operators
+    rng: ChaCha8Rng,
 }
 
 impl Population {
-    /// Set the mutation probability of the population
-    /// panics in debug mode if mp < 0.0 || mp > 1.0
+    /// Set the mutation probability of the population.
+    /// The value is not validated, use Cosyne::set_mutation_prob to reject values outside of [0, 1]
     pub fn set_mutation_prob(&mut self, mp: f64) {
         self.config.mutation_prob = mp;
     }
 
-    /// Set the mutation strength of the population
-    /// panics in debug mode if mp < 0.0 || mp > 1.0
+    /// Set the mutation strength of the population.
+    /// The value is not validated, use Cosyne::set_mutation_strength to reject negative
+    /// or non-finite values
     pub fn set_mutation_strength(&mut self, ms: f64) {
         self.config.mutation_strength = ms;
     }
 
-    /// Set the perturb probability of the population
-    /// panics in debug mode if pp < 0.0 || pp > 1.0
+    /// Set the perturb probability of the population.
+    /// The value is not validated, use Cosyne::set_perturb_prob to reject values outside of [0, 1]
     pub fn set_perturb_prob(&mut self, pp: f64) {
         self.config.perturb_prob = pp;
     }
@@ -44,24 +50,70 @@ impl Population {
     pub fn get_network(&self, j: usize) -> ANN {
         let genes: Vec<f64> = self.sub_populations[j].iter().map(|(v, _f)| *v).collect();
         let mut nn = self.network_topology.clone();
-        nn.set_genes(&genes);
+        nn.set_genes(&genes)
+            .expect("chromosomes have as many genes as the network topology");
 
         nn
     }
 
+    /// Replace the network at index j with the weights of a given network.
+    /// Its weights have no fitness yet.
+    /// returns an error if the network has a different number of genes
+    pub fn set_network(&mut self, j: usize, nn: &ANN) -> Result<(), CosyneError> {
+        self.set_network_with_fitness(j, nn, DEFAULT_FIT)
+    }
+
+    /// Replace the network at index j with the weights of a given network,
+    /// which all get the given fitness.
+    /// returns an error if the network has a different number of genes
+    pub(crate) fn set_network_with_fitness(
+        &mut self,
+        j: usize,
+        nn: &ANN,
+        fitness: f64,
+    ) -> Result<(), CosyneError> {
+        if nn.num_genes() != self.n {
+            return Err(CosyneError::GeneCountMismatch {
+                expected: self.n,
+                actual: nn.num_genes(),
+            });
+        }
+        self.sub_populations[j] = nn.genes().into_iter().map(|v| (v, fitness)).collect();
+        Ok(())
+    }
+
+    /// Return the mean fitness of the weights of a given network
+    pub(crate) fn mean_weight_fitness(&self, j: usize) -> f64 {
+        self.sub_populations[j].iter().map(|(_, f)| *f).sum::<f64>() / self.n as f64
+    }
+
     /// Return the fitness of a given network
     pub fn get_network_fitness(&self, j: usize) -> f64 {
         self.sub_populations[j].iter().map(|(_, f)| *f).sum()
     }
 
+    /// Return the standard deviation of the weight values within each sub-population
+    pub fn diversity(&self) -> Vec<f64> {
+        (0..self.n)
+            .map(|i| {
+                let vals: Vec<f64> = (0..self.m).map(|j| self.sub_populations[j][i].0).collect();
+                mean_std_dev(&vals).1
+            })
+            .collect()
+    }
+
     /// Create a new population with a given config and network
     pub fn new(config: Config, nn: &ANN) -> Population {
         let n: usize = nn.num_genes();
         let m: usize = config.pop_size;
+        let mut rng = match config.seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        };
 
         let mut sub_populations: Vec<Vec<(f64, f64)>> = Vec::new();
         for _i in 0..config.pop_size {
-            let genes: Vec<f64> = nn.randomize().genes();
+            let genes: Vec<f64> = nn.randomize(&mut rng).genes();
             let mut chromosome: Vec<(f64, f64)> = Vec::with_capacity(n);
             for v in &genes {
                 chromosome.push((*v, DEFAULT_FIT));
@@ -69,6 +121,18 @@ impl Population {
             sub_populations.push(chromosome);
         }
 
+        // spread latin hypercube initialized genes over the whole population
+        for (i, init) in nn.gene_initializers().iter().enumerate() {
+            if let Initializer::LatinHypercube(low, high) = init {
+                let mut strata: Vec<usize> = (0..m).collect();
+                strata.shuffle(&mut rng);
+                for (j, stratum) in strata.iter().enumerate() {
+                    let offset: f64 = (*stratum as f64 + rng.gen::<f64>()) / m as f64;
+                    sub_populations[j][i].0 = low + offset * (high - low);
+                }
+            }
+        }
+
         Population {
             config,
             network_topology: nn.clone(),
@@ -76,6 +140,7 @@ impl Population {
             n,
             m,
             current_generation: 0,
+            rng,
         }
     }
 
@@ -92,6 +157,11 @@ impl Population {
         let g: f64 = self.current_generation as f64;
         for (j, new_fit) in new_fits.iter().enumerate() {
             self.sub_populations[j].iter_mut().for_each(|(_, old_fit)| {
+                if *old_fit == DEFAULT_FIT {
+                    // new genes have no mean yet, and undoing it would overflow to -inf
+                    *old_fit = *new_fit;
+                    return;
+                }
                 *old_fit *= g; // undo the mean
                 *old_fit += new_fit; // add new fitness
                 *old_fit /= g + 1.0; // redo the mean
@@ -100,25 +170,42 @@ impl Population {
         self.current_generation += 1;
     }
 
+    /// Update the chromosome fitnesses with the objectives of every network,
+    /// scored by non-dominated sorting and crowding distance,
+    /// so the elite and the replaced weights are chosen by Pareto dominance.
+    /// The scores only rank the networks of one generation,
+    /// so they replace the previous fitnesses instead of being averaged with them
+    pub fn update_objectives(&mut self, objectives: &[Vec<f64>]) {
+        self.set_scores(&pareto::scores(objectives));
+    }
+
+    /// Replace the chromosome fitnesses with scores that are normalized within one generation,
+    /// e.g. Pareto or novelty scores, which are not comparable across generations
+    pub(crate) fn set_scores(&mut self, scores: &[f64]) {
+        for (chromosome, score) in self.sub_populations.iter_mut().zip(scores) {
+            chromosome.iter_mut().for_each(|(_, f)| *f = *score);
+        }
+        self.current_generation += 1;
+    }
+
+    /// Return the indices of all networks sorted by the mean fitness of their weights,
+    /// lower indices will have lower fitness, so the elite is at the end
+    pub(crate) fn ranking(&self) -> Vec<usize> {
+        let mean_fits: Vec<f64> = (0..self.m).map(|j| self.mean_weight_fitness(j)).collect();
+        let mut ranking: Vec<usize> = (0..self.m).collect();
+        ranking.sort_by(|a, b| mean_fits[*a].total_cmp(&mean_fits[*b]));
+        ranking
+    }
+
     /// Create offspring population from top n% of population
     fn spawn_offspring(&mut self) -> Vec<Vec<(f64, f64)>> {
         // find parents with highest mean fitness based on elite_threshold in config
-        // compute mean fitness of each column (network mean)
-        let mut mean_fits: Vec<(usize, f64)> = Vec::with_capacity(self.m);
-        for j in 0..self.m {
-            let mean_fit: f64 =
-                self.sub_populations[j].iter().map(|(_, f)| *f).sum::<f64>() / self.n as f64;
-            mean_fits.push((j, mean_fit));
-        }
-        // sort mean_fits by fitness, lower indices will have lower fitness
-        mean_fits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
         let elite_threshold: usize =
             (self.m as f64 * self.config.top_ratio_to_recombine).floor() as usize;
         let mut o: Vec<Vec<(f64, f64)>> = Vec::with_capacity(
             (self.config.top_ratio_to_recombine * self.m as f64).ceil() as usize,
         );
-        for (j, _f) in mean_fits.iter().take(elite_threshold) {
+        for j in self.ranking().iter().rev().take(elite_threshold) {
             o.push(self.sub_populations[*j].clone());
         }
 
@@ -129,49 +216,33 @@ impl Population {
         o
     }
 
-    /// Perform crossover mutation operator on offspring population,
-    fn crossover(&mut self, o: &mut Vec<Vec<(f64, f64)>>) {
+    /// Perform the configured crossover operator on pairs of the offspring population
+    fn crossover(&mut self, o: &mut [Vec<(f64, f64)>]) {
+        if o.len() < 2 {
+            // a single parent has no partner to recombine with
+            return;
+        }
         // create vec of deranged indices, not sure if actually good but should be good enough
-        let deranged = random_derangement(o.len());
-
-        let mut rng = thread_rng();
-        let d = Normal::new(self.n as f64 / 2.0, self.n as f64 * 0.33).unwrap();
-        for (p1, p2) in (0..o.len()).zip(&deranged) {
-            // TODO: different user defined crossover methods
-
-            let cross_p: f64 = d.sample(&mut rng);
-            // clip to min and max
-            let crossover_point: usize = if cross_p < 0.0 {
-                0
-            } else if cross_p > self.n as f64 {
-                self.n
-            } else {
-                cross_p.round() as usize
-            };
-
-            // perform single point crossover
-            for i in 0..crossover_point {
-                // switch chromosomes and fitness between parent 1 and 2
-                let old_p1_vals = self.sub_populations[p1][i];
-                self.sub_populations[p1][i] = self.sub_populations[*p2][i];
-                self.sub_populations[*p2][i] = old_p1_vals;
-            }
+        let deranged = random_derangement(o.len(), &mut self.rng);
+
+        for (p1, p2) in (0..o.len()).zip(deranged) {
+            let (c1, c2) = pair_mut(o, p1, p2);
+            self.config.crossover.apply(c1, c2, &mut self.rng);
         }
     }
 
     /// Perform a mutation operator on offspring population,
     /// by either perturbing or completely replacing values
-    fn mutate(&self, o: &mut [Vec<(f64, f64)>]) {
-        // TODO: user defined mutation distribution in case of pertubation
-        let d = Normal::new(0.0, 0.4).unwrap();
-        let mut rng = rand::thread_rng();
+    fn mutate(&mut self, o: &mut [Vec<(f64, f64)>]) {
+        let config = &self.config;
+        let rng = &mut self.rng;
 
         o.iter_mut().flatten().for_each(|(v, f)| {
-            if rng.gen::<f64>() < self.config.mutation_prob {
-                if rng.gen::<f64>() < self.config.perturb_prob {
-                    *v += rng.sample(d) * self.config.mutation_strength;
+            if rng.gen::<f64>() < config.mutation_prob {
+                if rng.gen::<f64>() < config.perturb_prob {
+                    *v += config.perturb_distribution.sample(rng) * config.mutation_strength;
                 } else {
-                    *v = (rng.gen::<f64>() * 2.0 - 1.0) * self.config.mutation_strength;
+                    *v = config.replace_distribution.sample(rng) * config.mutation_strength;
                 }
                 *f = DEFAULT_FIT;
             }
@@ -181,23 +252,23 @@ impl Population {
     /// Replace the least fit chromosome in each sub-population with newly created offspring
     /// Also permute the left over original chromosomes among each other in the sub-population
     fn replace_and_permute(&mut self, o: &[Vec<(f64, f64)>]) {
-        let mut rng = thread_rng();
         for i in 0..self.n {
             // sort the sub-population
             let mut genes: Vec<(f64, f64)> = Vec::with_capacity(self.m);
             for j in 0..self.m {
                 genes.push(self.sub_populations[j][i]);
             }
-            genes.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            genes.sort_by(|a, b| a.1.total_cmp(&b.1));
             let fit_threshold: f64 =
                 genes[(self.config.top_ratio_to_recombine * genes.len() as f64).floor() as usize].1;
 
-            let mut offspring_idx: usize = 0;
+            let mut offspring = o.iter();
             for j in 0..self.m {
                 if self.sub_populations[j][i].1 < fit_threshold {
                     // replace least fit
-                    self.sub_populations[j][i] = o[offspring_idx][i];
-                    offspring_idx += 1;
+                    if let Some(chromosome) = offspring.next() {
+                        self.sub_populations[j][i] = chromosome[i];
+                    }
                 }
             }
 
@@ -207,18 +278,20 @@ impl Population {
                 (0..self.m).map(|j| self.sub_populations[j][i].1).collect();
             let mut marked: Vec<usize> = vec![];
             for j in 0..self.m {
-                let prob: f64 = self
-                    .config
-                    .permutation_prob_f
-                    .get_probability(&sub_pop_fits, self.sub_populations[j][i].1);
-                if rng.gen::<f64>() < prob {
+                let prob: f64 = self.config.permutation_prob_f.get_probability(
+                    &sub_pop_fits,
+                    self.sub_populations[j][i].1,
+                    self.n,
+                );
+                if self.rng.gen::<f64>() < prob {
                     // mark for permutation
                     marked.push(j);
                 }
             }
 
             if marked.is_empty() {
-                return;
+                // nothing to permute in this sub-population, continue with the next one
+                continue;
             }
 
             // permute marked by shifting among them
@@ -231,10 +304,20 @@ impl Population {
     }
 }
 
-/// Create random permutations without fixed points a.k.a. derangement
-fn random_derangement(length: usize) -> Vec<usize> {
-    let mut rng = thread_rng();
+/// Borrow two distinct chromosomes mutably at once
+fn pair_mut<T>(v: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    debug_assert_ne!(a, b);
+    if a < b {
+        let (left, right) = v.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = v.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
 
+/// Create random permutations without fixed points a.k.a. derangement
+fn random_derangement<R: Rng>(length: usize, rng: &mut R) -> Vec<usize> {
     'l: loop {
         let mut v: Vec<usize> = (0..length).collect();
         for j in (1..length).rev() {
@@ -254,10 +337,64 @@ fn random_derangement(length: usize) -> Vec<usize> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn latin_hypercube_initialization() {
+        let mut nn = ANN::new(2, 1, crate::Activation::Linear);
+        nn.set_initializer(Initializer::LatinHypercube(-1.0, 1.0), Initializer::Zeros)
+            .unwrap();
+        let pop = Population::new(Config::new(10), &nn);
+
+        // every stratum of width 0.2 contains exactly one weight of each sub-population
+        for i in 0..2 {
+            let mut strata: Vec<usize> = (0..10)
+                .map(|j| ((pop.sub_populations[j][i].0 + 1.0) / 0.2).floor() as usize)
+                .collect();
+            strata.sort_unstable();
+            assert_eq!(strata, (0..10).collect::<Vec<usize>>());
+        }
+        // biases are not spread
+        assert!((0..10).all(|j| pop.sub_populations[j][2].0 == 0.0));
+    }
+
+    #[test]
+    fn update_fitnesses_new_genes() {
+        let nn = ANN::new(2, 1, crate::Activation::Linear);
+        let mut pop = Population::new(Config::new(4), &nn);
+        pop.update_fitnesses(&[1.0, 2.0, 3.0, 4.0]);
+        pop.sub_populations[0][0].1 = DEFAULT_FIT;
+        pop.update_fitnesses(&[3.0, 2.0, 1.0, 0.0]);
+
+        // new genes take the new fitness instead of averaging with the default fitness
+        assert_eq!(pop.sub_populations[0][0].1, 3.0);
+        assert_eq!(pop.sub_populations[0][1].1, 2.0);
+        assert_eq!(pop.sub_populations[3][0].1, 2.0);
+    }
+
+    #[test]
+    fn update_objectives_overwrites() {
+        let nn = ANN::new(2, 1, crate::Activation::Linear);
+        let mut pop = Population::new(Config::new(2), &nn);
+        pop.update_objectives(&[vec![1.0, 1.0], vec![0.0, 0.0]]);
+        pop.update_objectives(&[vec![0.0, 0.0], vec![1.0, 1.0]]);
+
+        // the second network dominates in the last generation only
+        assert_eq!(pop.sub_populations[0][0].1, -0.5);
+        assert_eq!(pop.sub_populations[1][0].1, 0.5);
+    }
+
+    #[test]
+    fn crossover_single_parent() {
+        let nn = ANN::new(2, 1, crate::Activation::Linear);
+        let mut pop = Population::new(Config::new(4), &nn);
+        let mut o = vec![pop.sub_populations[0].clone()];
+        pop.crossover(&mut o);
+        assert_eq!(o[0], pop.sub_populations[0]);
+    }
+
     #[test]
     fn test_random_derangement() {
         let length: usize = 10;
-        let d = random_derangement(length);
+        let d = random_derangement(length, &mut rand::thread_rng());
         println!("d: {:?}", d);
         assert_eq!(d.len(), length);
         assert!(!d.iter().zip(0..length).any(|(d, i)| *d == i));
@@ -0,0 +1,87 @@
+use crate::GenerationStats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+/// Re-initialization of the Population once it stopped making progress (IPOP-style).
+/// The champion, the history and all archives are kept across restarts.
+/// At least one of stagnation and min_diversity has to be set
+pub struct RestartStrategy {
+    /// Restart if the best fitness of a generation did not improve
+    /// during the given number of generations since the last restart
+    pub stagnation: Option<usize>,
+    /// Restart if the mean diversity of the sub-populations,
+    /// which is the standard deviation of their weights, falls below the given value
+    pub min_diversity: Option<f64>,
+    /// Double pop_size at every restart
+    pub double_pop_size: bool,
+    /// Insert the champion into every new Population
+    pub keep_champion: bool,
+}
+
+impl RestartStrategy {
+    /// Return true if the population evolved since the last restart has to be restarted,
+    /// given the statistics of its generations
+    pub(crate) fn should_restart(&self, history: &[GenerationStats]) -> bool {
+        if let (Some(k), Some(last)) = (self.stagnation, history.last()) {
+            let bests: Vec<f64> = history.iter().map(|s| s.best).collect();
+            if bests.len() > k {
+                let (before, recent) = bests.split_at(bests.len() - k);
+                let max = |v: &[f64]| v.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                if max(recent) <= max(before) {
+                    debug!("gen {}, restarting after stagnation", last.generation);
+                    return true;
+                }
+            }
+        }
+        if let (Some(min), Some(last)) = (self.min_diversity, history.last()) {
+            let mean = last.diversity.iter().sum::<f64>() / last.diversity.len().max(1) as f64;
+            if mean < min {
+                debug!(
+                    "gen {}, restarting with diversity {:.4}",
+                    last.generation, mean
+                );
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn history(bests: &[f64], diversity: f64) -> Vec<GenerationStats> {
+        bests
+            .iter()
+            .enumerate()
+            .map(|(g, best)| {
+                GenerationStats::new(g, *best, &[*best], vec![diversity], 0, 0, Duration::ZERO)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stagnation() {
+        let strategy = RestartStrategy {
+            stagnation: Some(2),
+            ..Default::default()
+        };
+        assert!(!strategy.should_restart(&[]));
+        assert!(!strategy.should_restart(&history(&[1.0, 1.0], 1.0)));
+        assert!(!strategy.should_restart(&history(&[1.0, 1.0, 2.0], 1.0)));
+        assert!(strategy.should_restart(&history(&[1.0, 2.0, 2.0, 1.5], 1.0)));
+    }
+
+    #[test]
+    fn diversity() {
+        let strategy = RestartStrategy {
+            min_diversity: Some(0.1),
+            ..Default::default()
+        };
+        assert!(!strategy.should_restart(&history(&[1.0], 0.2)));
+        assert!(strategy.should_restart(&history(&[1.0], 0.05)));
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumerate the ways of ranking networks and weights for recombination and replacement
+pub enum Selection {
+    /// Rank by fitness, which is the sum of all objectives
+    /// for a MultiObjectiveEnvironment
+    #[default]
+    Fitness,
+    /// Rank by non-dominated sorting and crowding distance of the objectives (NSGA-II).
+    /// Networks in better fronts and less crowded regions get higher scores,
+    /// which take the place of the fitness in the Population
+    Pareto,
+    /// Rank by a blend of fitness and novelty, with the given weight of the novelty
+    /// in the range [0, 1]. Both are normalized to [0, 1] within each generation,
+    /// a weight of 1 is pure novelty search.
+    /// Requires a BehaviorEnvironment, see Cosyne::new_with_behavior
+    Novelty(f64),
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Statistics of a single generation, collected by every call to Cosyne::evolve
+pub struct GenerationStats {
+    /// index of the generation, starting at 0
+    pub generation: usize,
+    /// fitness of the champion after this generation
+    pub champion: f64,
+    /// highest network fitness of this generation
+    pub best: f64,
+    /// mean network fitness of this generation
+    pub mean: f64,
+    /// median network fitness of this generation
+    pub median: f64,
+    /// lowest network fitness of this generation
+    pub worst: f64,
+    /// standard deviation of the network fitnesses of this generation
+    pub std_dev: f64,
+    /// standard deviation of the weight values within each sub-population,
+    /// so there is one entry for every gene of the network
+    pub diversity: Vec<f64>,
+    /// total number of network evaluations so far
+    pub evaluations: usize,
+    /// number of evaluations of this generation that returned a NaN or infinite fitness,
+    /// which was handled according to the NonFinitePolicy of the Config
+    pub non_finite: usize,
+    /// wall time elapsed since the start of the run
+    pub wall_time: Duration,
+}
+
+impl GenerationStats {
+    /// Summarize the fitnesses of all networks of one generation
+    pub(crate) fn new(
+        generation: usize,
+        champion: f64,
+        fits: &[f64],
+        diversity: Vec<f64>,
+        evaluations: usize,
+        non_finite: usize,
+        wall_time: Duration,
+    ) -> Self {
+        let mut sorted: Vec<f64> = fits.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let (mean, std_dev) = mean_std_dev(fits);
+        Self {
+            generation,
+            champion,
+            best: sorted.last().copied().unwrap_or(f64::NAN),
+            mean,
+            median: median(&sorted),
+            worst: sorted.first().copied().unwrap_or(f64::NAN),
+            std_dev,
+            diversity,
+            evaluations,
+            non_finite,
+            wall_time,
+        }
+    }
+
+    /// Mean diversity over all sub-populations
+    pub fn mean_diversity(&self) -> f64 {
+        mean_std_dev(&self.diversity).0
+    }
+}
+
+/// Return the mean and population standard deviation of the values
+pub(crate) fn mean_std_dev(vals: &[f64]) -> (f64, f64) {
+    if vals.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    let n = vals.len() as f64;
+    let mean = vals.iter().sum::<f64>() / n;
+    let var = vals.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+    (mean, var.sqrt())
+}
+
+/// Return the median of already sorted values
+pub(crate) fn median(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        f64::NAN
+    } else if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generation_stats() {
+        let fits: Vec<f64> = vec![4.0, 1.0, 3.0, 2.0];
+        let stats = GenerationStats::new(3, 5.0, &fits, vec![0.5, 1.5], 40, 0, Duration::ZERO);
+
+        assert_eq!(stats.best, 4.0);
+        assert_eq!(stats.worst, 1.0);
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.std_dev, 1.25_f64.sqrt());
+        assert_eq!(stats.mean_diversity(), 1.0);
+    }
+
+    #[test]
+    fn median_odd() {
+        assert_eq!(median(&[1.0, 2.0, 10.0]), 2.0);
+        assert!(median(&[]).is_nan());
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use crate::{config::check_count, CosyneError, ANN};
+
+#[derive(Debug, Clone)]
+/// Criteria for terminating an optimization run started with Cosyne::run.
+/// Generations, evaluations and wall time are counted from the start of the optimization,
+/// including the part of a run before it was checkpointed
+pub enum StopCondition {
+    /// Stop after the given number of generations
+    MaxGenerations(usize),
+    /// Stop once at least the given number of networks has been evaluated
+    MaxEvaluations(usize),
+    /// Stop once the champion reaches at least the given fitness
+    TargetFitness(f64),
+    /// Stop once the given wall time has elapsed
+    WallClock(Duration),
+    /// Stop if the champion did not improve during the given number of generations, at least 1
+    Stagnation(usize),
+    /// Stop as soon as any of the conditions is met, needs at least one condition
+    Any(Vec<StopCondition>),
+    /// Stop once all of the conditions are met, needs at least one condition
+    All(Vec<StopCondition>),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// Enumerate the reasons why a run stopped
+pub enum StopReason {
+    /// The maximum number of generations was reached
+    MaxGenerations,
+    /// The maximum number of evaluations was reached
+    MaxEvaluations,
+    /// The champion reached the target fitness
+    TargetFitness,
+    /// The wall clock budget was used up
+    WallClock,
+    /// The champion did not improve for too long
+    Stagnation,
+    /// A registered CosyneObserver requested to stop
+    Observer,
+}
+
+#[derive(Debug, Clone)]
+/// Outcome of an optimization run
+pub struct RunReport {
+    /// Every condition that was met when the run stopped
+    pub reasons: Vec<StopReason>,
+    /// Number of generations evolved during this run
+    pub generations: usize,
+    /// Total number of network evaluations
+    pub evaluations: usize,
+    /// Wall time spent during this run
+    pub elapsed: Duration,
+    /// The final champion and its fitness
+    pub champion: (ANN, f64),
+}
+
+/// Current state of a run, which the stop conditions are checked against
+pub(crate) struct Progress<'a> {
+    pub(crate) generation: usize,
+    pub(crate) evaluations: usize,
+    pub(crate) champion_fitness: f64,
+    pub(crate) champion_fit_history: &'a [f64],
+    pub(crate) elapsed: Duration,
+}
+
+impl StopCondition {
+    /// Check that no Any or All combines an empty list of conditions,
+    /// which would never or immediately stop, and that Stagnation spans at least one generation
+    pub(crate) fn validate(&self) -> Result<(), CosyneError> {
+        match self {
+            StopCondition::Stagnation(k) => check_count("stagnation generations", *k),
+            StopCondition::Any(conditions) | StopCondition::All(conditions) => {
+                if conditions.is_empty() {
+                    return Err(CosyneError::EmptyStopCondition);
+                }
+                conditions.iter().try_for_each(|c| c.validate())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Return the reasons for stopping, if the condition is met by the current state of the run
+    pub(crate) fn check(&self, progress: &Progress) -> Option<Vec<StopReason>> {
+        let met = |met: bool, reason: StopReason| if met { Some(vec![reason]) } else { None };
+        match self {
+            StopCondition::MaxGenerations(n) => {
+                met(progress.generation >= *n, StopReason::MaxGenerations)
+            }
+            StopCondition::MaxEvaluations(n) => {
+                met(progress.evaluations >= *n, StopReason::MaxEvaluations)
+            }
+            StopCondition::TargetFitness(f) => {
+                met(progress.champion_fitness >= *f, StopReason::TargetFitness)
+            }
+            StopCondition::WallClock(d) => met(progress.elapsed >= *d, StopReason::WallClock),
+            StopCondition::Stagnation(k) => {
+                let h = progress.champion_fit_history;
+                met(
+                    h.len() > *k && h[h.len() - 1] <= h[h.len() - 1 - k],
+                    StopReason::Stagnation,
+                )
+            }
+            StopCondition::Any(conditions) => {
+                let reasons: Vec<StopReason> = conditions
+                    .iter()
+                    .filter_map(|c| c.check(progress))
+                    .flatten()
+                    .collect();
+                if reasons.is_empty() {
+                    None
+                } else {
+                    Some(reasons)
+                }
+            }
+            StopCondition::All(conditions) => {
+                let mut reasons: Vec<StopReason> = vec![];
+                for c in conditions {
+                    reasons.append(&mut c.check(progress)?);
+                }
+                Some(reasons)
+            }
+        }
+    }
+}
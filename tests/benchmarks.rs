@@ -0,0 +1,19 @@
+use cosyne::benchmarks::CartPole;
+use cosyne::{Activation, Config, Cosyne, ANN};
+
+#[test]
+fn cart_pole() {
+    let mut config = Config::new(50);
+    config.seed = Some(1);
+    let env = Box::new(CartPole::with_max_steps(1000));
+    let nn = ANN::new(4, 1, Activation::Tanh);
+    let mut cosyne = Cosyne::new(env, nn, config);
+    for _ in 0..300 {
+        cosyne.evolve().unwrap();
+        if cosyne.champion().1 >= 1000.0 {
+            break;
+        }
+    }
+    let champion = cosyne.champion();
+    assert_eq!(champion.1, 1000.0);
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "serde")]
+
+use cosyne::benchmarks::Xor;
+use cosyne::{Activation, Config, Cosyne, ANN};
+
+#[test]
+fn resume_from_checkpoint() {
+    let mut config = Config::new(50);
+    config.seed = Some(7);
+    let mut nn = ANN::new(2, 1, Activation::Relu);
+    nn.add_layer(2, Activation::Relu);
+    let mut cosyne = Cosyne::new(Box::new(Xor::new()), nn, config);
+    for _ in 0..10 {
+        cosyne.evolve().unwrap();
+    }
+
+    let path = std::env::temp_dir().join("cosyne_resume_from_checkpoint.bin");
+    cosyne.save_checkpoint(&path).unwrap();
+    let mut resumed = Cosyne::load_checkpoint(&path, Box::new(Xor::new())).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    for _ in 0..10 {
+        cosyne.evolve().unwrap();
+        resumed.evolve().unwrap();
+    }
+    assert_eq!(
+        cosyne.champion_fit_history(),
+        resumed.champion_fit_history()
+    );
+    assert_eq!(cosyne.champion().0.genes(), resumed.champion().0.genes());
+}
@@ -0,0 +1,64 @@
+use cosyne::{Activation, Config, Cosyne, CosyneError, Environment, ANN};
+
+struct ConstEnvironment {}
+
+impl Environment for ConstEnvironment {
+    fn evaluate(&self, _nn: &mut ANN) -> f64 {
+        1.0
+    }
+}
+
+fn try_cosyne(config: Config) -> Result<Cosyne, CosyneError> {
+    Cosyne::try_new(
+        Box::new(ConstEnvironment {}),
+        ANN::new(2, 1, Activation::Linear),
+        config,
+    )
+}
+
+#[test]
+fn try_new_invalid_config() {
+    let mut config = Config::new(1);
+    assert!(matches!(
+        try_cosyne(config),
+        Err(CosyneError::TooFewParents { pop_size: 1, .. })
+    ));
+
+    config = Config::new(20);
+    config.top_ratio_to_recombine = 0.0;
+    assert!(matches!(
+        try_cosyne(config),
+        Err(CosyneError::InvalidTopRatio(_))
+    ));
+
+    config = Config::new(20);
+    config.mutation_prob = -0.1;
+    let err = try_cosyne(config).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "mutation_prob has to be in the range [0, 1], got -0.1"
+    );
+}
+
+#[test]
+fn setters_reject_invalid_values() {
+    let mut cosyne = try_cosyne(Config::new(20)).unwrap();
+    assert_eq!(cosyne.set_mutation_prob(0.1), Ok(()));
+    assert_eq!(cosyne.set_mutation_strength(2.0), Ok(()));
+    assert_eq!(cosyne.set_perturb_prob(1.0), Ok(()));
+
+    assert!(cosyne.set_mutation_prob(1.1).is_err());
+    assert!(cosyne.set_mutation_strength(f64::INFINITY).is_err());
+    assert!(cosyne.set_perturb_prob(f64::NAN).is_err());
+    cosyne.evolve().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "invalid config")]
+fn new_panics_on_invalid_config() {
+    Cosyne::new(
+        Box::new(ConstEnvironment {}),
+        ANN::new(2, 1, Activation::Linear),
+        Config::new(4),
+    );
+}
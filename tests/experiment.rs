@@ -0,0 +1,75 @@
+use std::fs;
+
+use cosyne::{
+    benchmarks::Xor,
+    experiment::{write_runs_csv, write_summary_csv, Experiment, Param, Search},
+    Activation, Config, Environment, StopCondition, ANN,
+};
+
+fn network() -> ANN {
+    let mut nn = ANN::new(2, 1, Activation::Relu);
+    nn.add_layer(3, Activation::Sigmoid);
+    nn
+}
+
+#[test]
+fn grid_experiment() {
+    let stop = StopCondition::Any(vec![
+        StopCondition::MaxGenerations(20),
+        StopCondition::TargetFitness(4.0),
+    ]);
+    let experiment = Experiment::new(
+        Config::new(20),
+        network(),
+        |_seed| -> Box<dyn Environment> { Box::new(Xor::new()) },
+        stop,
+    )
+    .search(Search::Grid(vec![
+        (Param::MutationProb, vec![0.2, 0.4]),
+        (Param::PopSize, vec![20.0, 30.0]),
+    ]))
+    .seeds(3)
+    .target_fitness(4.0);
+    let results = experiment.run().unwrap();
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[3].config.mutation_prob, 0.4);
+    assert_eq!(results[3].config.pop_size, 30);
+    for r in &results {
+        assert_eq!(r.runs.len(), 3);
+        for (seed, run) in r.runs.iter().enumerate() {
+            assert_eq!(run.seed, seed as u64);
+            // runs stop at the target, so reaching it took all evaluations
+            if run.fitness >= 4.0 {
+                assert_eq!(run.evaluations_to_target, Some(run.evaluations));
+            } else {
+                assert_eq!(run.evaluations_to_target, None);
+                assert_eq!(run.evaluations, 20 * r.config.pop_size);
+            }
+        }
+        let fitness = r.fitness();
+        assert!(fitness.q1 <= fitness.median && fitness.median <= fitness.q3);
+    }
+
+    let dir = std::env::temp_dir();
+    let summary = dir.join("cosyne_experiment_summary.csv");
+    let runs = dir.join("cosyne_experiment_runs.csv");
+    write_summary_csv(&results, &summary).unwrap();
+    write_runs_csv(&results, &runs).unwrap();
+    let summary = fs::read_to_string(summary).unwrap();
+    assert!(summary.starts_with("mutation_prob,pop_size,runs,success_rate,"));
+    assert_eq!(summary.lines().count(), 5);
+    assert_eq!(fs::read_to_string(runs).unwrap().lines().count(), 13);
+}
+
+#[test]
+fn invalid_configuration() {
+    let experiment = Experiment::new(
+        Config::new(20),
+        network(),
+        |_seed| -> Box<dyn Environment> { Box::new(Xor::new()) },
+        StopCondition::MaxGenerations(1),
+    )
+    .search(Search::Grid(vec![(Param::PerturbProb, vec![0.5, 1.5])]));
+    assert!(experiment.run().is_err());
+}
@@ -0,0 +1,155 @@
+use cosyne::{
+    Activation, Config, Cosyne, CosyneError, Environment, IslandModel, MigrationTopology,
+    NonFinitePolicy, PermutationProbF, StopCondition, ANN,
+};
+
+/// Deterministic environment rewarding large weights
+struct SumOfWeights {}
+
+impl Environment for SumOfWeights {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        nn.genes().iter().sum()
+    }
+}
+
+/// Returns NaN for every network
+struct Nan {}
+
+impl Environment for Nan {
+    fn evaluate(&self, _nn: &mut ANN) -> f64 {
+        f64::NAN
+    }
+}
+
+fn island(seed: u64, hidden: usize) -> Cosyne {
+    let config = Config::builder().pop_size(20).seed(seed).build().unwrap();
+    let mut nn = ANN::new(2, 1, Activation::Linear);
+    nn.add_layer(hidden, Activation::Linear);
+    Cosyne::new(Box::new(SumOfWeights {}), nn, config)
+}
+
+#[test]
+fn run_reports_champions() {
+    let mut model = IslandModel::builder((0..3).map(|seed| island(seed, 2)).collect())
+        .topology(MigrationTopology::Random)
+        .migration_interval(2)
+        .migrants(2)
+        .seed(0)
+        .build()
+        .unwrap();
+    let report = model.run(StopCondition::MaxGenerations(10)).unwrap();
+
+    assert_eq!(report.global.generations, 10);
+    assert_eq!(report.island_champions.len(), 3);
+    let best = report
+        .island_champions
+        .iter()
+        .map(|c| c.1)
+        .fold(f64::MIN, f64::max);
+    assert_eq!(report.global.champion.1, best);
+    assert_eq!(
+        report.global.evaluations,
+        model
+            .islands()
+            .iter()
+            .map(|i| i.evaluations())
+            .sum::<usize>()
+    );
+    assert_eq!(model.champion_fit_history().len(), 10);
+    assert_eq!(*model.champion_fit_history().last().unwrap(), best);
+}
+
+#[test]
+fn migrants_are_evaluated_by_their_destination() {
+    // without permutation, the immigrants keep the fitness of the fittest network
+    // and survive breeding unchanged
+    let islands = (0..2)
+        .map(|seed| {
+            let config = Config::builder()
+                .pop_size(20)
+                .permutation_prob_f(PermutationProbF::Uniform(0.0))
+                .seed(seed)
+                .build()
+                .unwrap();
+            let mut nn = ANN::new(2, 1, Activation::Linear);
+            nn.add_layer(2, Activation::Linear);
+            Cosyne::new(Box::new(SumOfWeights {}), nn, config)
+        })
+        .collect();
+    let mut model = IslandModel::builder(islands)
+        .migration_interval(1)
+        .build()
+        .unwrap();
+    for _ in 0..5 {
+        model.evolve().unwrap();
+    }
+
+    // so the best network of each island is evaluated by the other one
+    // in the following generation
+    let (a, b) = (model.islands()[0].history(), model.islands()[1].history());
+    for g in 1..5 {
+        assert!(a[g].best >= b[g - 1].best);
+        assert!(b[g].best >= a[g - 1].best);
+    }
+}
+
+#[test]
+fn invalid_model() {
+    let model = IslandModel::builder(vec![island(0, 2), island(1, 2)])
+        .migration_interval(0)
+        .build();
+    assert!(matches!(
+        model,
+        Err(CosyneError::MustBePositive {
+            name: "migration_interval"
+        })
+    ));
+
+    let model = IslandModel::builder(vec![island(0, 2), island(1, 2)])
+        .migrants(0)
+        .build();
+    assert!(matches!(
+        model,
+        Err(CosyneError::MustBePositive { name: "migrants" })
+    ));
+
+    let model = IslandModel::builder(vec![]).build();
+    assert!(matches!(
+        model,
+        Err(CosyneError::MustBePositive {
+            name: "number of islands"
+        })
+    ));
+
+    let model = IslandModel::builder(vec![island(0, 2), island(1, 3)]).build();
+    assert!(matches!(
+        model,
+        Err(CosyneError::GeneCountMismatch {
+            expected: 9,
+            actual: 13
+        })
+    ));
+}
+
+#[test]
+fn failed_generation_is_not_recorded() {
+    let config = Config::builder()
+        .pop_size(20)
+        .non_finite_policy(NonFinitePolicy::Abort)
+        .build()
+        .unwrap();
+    let mut nn = ANN::new(2, 1, Activation::Linear);
+    nn.add_layer(2, Activation::Linear);
+    let failing = Cosyne::new(Box::new(Nan {}), nn, config);
+    let mut model = IslandModel::builder(vec![island(0, 2), failing])
+        .build()
+        .unwrap();
+    assert!(matches!(
+        model.evolve(),
+        Err(CosyneError::NonFiniteFitness { .. })
+    ));
+    // the first island was evaluated successfully, but did not record the generation
+    assert_eq!(model.generation(), 0);
+    assert_eq!(model.evaluations(), 0);
+    assert!(model.islands()[0].history().is_empty());
+}
@@ -0,0 +1,100 @@
+use cosyne::{
+    Activation, BehaviorEnvironment, Config, Cosyne, CosyneError, Environment, MapElites, ANN,
+};
+
+/// The behavior is the output of the network, the fitness rewards outputs close to 0
+struct Output {}
+
+impl BehaviorEnvironment for Output {
+    fn evaluate(&self, nn: &mut ANN) -> (f64, Vec<f64>) {
+        let y = nn.forward(vec![1.0])[0];
+        (-y.abs(), vec![y])
+    }
+}
+
+/// Environment without behavior descriptors
+struct Constant {}
+
+impl Environment for Constant {
+    fn evaluate(&self, _nn: &mut ANN) -> f64 {
+        1.0
+    }
+}
+
+fn cosyne(seed: u64) -> Cosyne {
+    let config = Config::builder().pop_size(20).seed(seed).build().unwrap();
+    let nn = ANN::new(1, 1, Activation::Linear);
+    let mut cosyne = Cosyne::new_with_behavior(Box::new(Output {}), nn, config);
+    cosyne
+        .set_map_elites(MapElites::new(&[(-2.0, 2.0, 8)]).unwrap())
+        .unwrap();
+    cosyne
+}
+
+#[test]
+fn archive_collects_elites() {
+    let mut cosyne = cosyne(0);
+    for _ in 0..10 {
+        cosyne.evolve().unwrap();
+    }
+
+    let archive = cosyne.map_elites().unwrap();
+    assert!(archive.filled_cells() > 1);
+    assert_eq!(archive.coverage(), archive.filled_cells() as f64 / 8.0);
+    let qd_score: f64 = archive.elites().map(|e| e.fitness).sum();
+    assert_eq!(archive.qd_score(), qd_score);
+    for elite in archive.elites() {
+        assert_eq!(archive.cell(&elite.behavior), Ok(elite.cell.clone()));
+        let (fit, behavior) = Output {}.evaluate(&mut elite.network.clone());
+        assert_eq!((fit, behavior), (elite.fitness, elite.behavior.clone()));
+    }
+    // the champion is the best elite, as every network is inserted into the archive
+    assert_eq!(archive.best().unwrap().fitness, cosyne.champion().1);
+}
+
+#[test]
+fn seed_from_archive() {
+    let mut first = cosyne(0);
+    for _ in 0..10 {
+        first.evolve().unwrap();
+    }
+    let archive = first.map_elites().unwrap().clone();
+
+    let mut second = cosyne(1);
+    second.set_map_elites(archive.clone()).unwrap();
+    assert_eq!(second.seed_from_map_elites(), Ok(archive.filled_cells()));
+    second.evolve().unwrap();
+    assert!(second.champion().1 >= archive.best().unwrap().fitness);
+}
+
+#[test]
+fn behavior_length_mismatch() {
+    let config = Config::builder().pop_size(20).seed(0).build().unwrap();
+    let nn = ANN::new(1, 1, Activation::Linear);
+    let mut cosyne = Cosyne::new_with_behavior(Box::new(Output {}), nn, config);
+    cosyne
+        .set_map_elites(MapElites::new(&[(-2.0, 2.0, 8), (-2.0, 2.0, 8)]).unwrap())
+        .unwrap();
+    assert_eq!(
+        cosyne.evolve(),
+        Err(CosyneError::BehaviorLengthMismatch {
+            expected: 2,
+            actual: 1
+        })
+    );
+    // nothing was recorded before the error
+    assert_eq!(cosyne.evaluations(), 0);
+    assert!(cosyne.history().is_empty());
+    assert_eq!(cosyne.champion().1, f64::MIN);
+    assert_eq!(cosyne.map_elites().unwrap().filled_cells(), 0);
+}
+
+#[test]
+fn map_elites_requires_behavior() {
+    let nn = ANN::new(1, 1, Activation::Linear);
+    let mut cosyne = Cosyne::new(Box::new(Constant {}), nn, Config::new(20));
+    assert_eq!(
+        cosyne.set_map_elites(MapElites::new(&[(-2.0, 2.0, 8)]).unwrap()),
+        Err(CosyneError::MissingBehavior)
+    );
+}
@@ -0,0 +1,72 @@
+use cosyne::{Activation, Config, Cosyne, Environment, MultiObjectiveEnvironment, Selection, ANN};
+
+/// Two conflicting objectives: the output should be close to 1 and close to -1,
+/// so every output in [-1, 1] is Pareto optimal
+struct TradeOff {}
+
+impl MultiObjectiveEnvironment for TradeOff {
+    fn evaluate(&self, nn: &mut ANN) -> Vec<f64> {
+        let y = nn.forward(vec![1.0])[0];
+        vec![-(y - 1.0).powi(2), -(y + 1.0).powi(2)]
+    }
+}
+
+struct Constant {}
+
+impl Environment for Constant {
+    fn evaluate(&self, _nn: &mut ANN) -> f64 {
+        1.0
+    }
+}
+
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+#[test]
+fn pareto_front() {
+    let config = Config::builder()
+        .pop_size(30)
+        .selection(Selection::Pareto)
+        .seed(0)
+        .build()
+        .unwrap();
+    let nn = ANN::new(1, 1, Activation::Linear);
+    let mut cosyne = Cosyne::new_multi_objective(Box::new(TradeOff {}), nn, config);
+    for _ in 0..30 {
+        cosyne.evolve().unwrap();
+    }
+
+    let front = cosyne.pareto_front();
+    assert!(front.len() > 1 && front.len() <= 30);
+    for (i, (_, a)) in front.iter().enumerate() {
+        assert_eq!(a.len(), 2);
+        // outputs close to [-1, 1] are not much worse than -4 in both objectives
+        assert!(a.iter().all(|v| *v > -4.5));
+        for (j, (_, b)) in front.iter().enumerate() {
+            assert!(i == j || !dominates(a, b));
+        }
+    }
+    // the objectives belong to the stored network
+    let (nn, objectives) = &front[0];
+    assert_eq!(&TradeOff {}.evaluate(&mut nn.clone()), objectives);
+    // the champion maximizes the sum of the objectives
+    assert!(front
+        .iter()
+        .all(|(_, o)| o.iter().sum::<f64>() <= cosyne.champion().1));
+}
+
+#[test]
+fn single_objective_front_is_champion() {
+    let mut cosyne = Cosyne::new(
+        Box::new(Constant {}),
+        ANN::new(2, 1, Activation::Linear),
+        Config::new(10),
+    );
+    cosyne.evolve().unwrap();
+    cosyne.evolve().unwrap();
+
+    let front = cosyne.pareto_front();
+    assert_eq!(front.len(), 1);
+    assert_eq!(front[0].1, vec![cosyne.champion().1]);
+}
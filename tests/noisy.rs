@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cosyne::{Activation, ChampionPolicy, Config, Cosyne, Environment, FitnessAggregation, ANN};
+
+/// The very first evaluation is a lucky outlier, all others return the sum of the weights
+struct LuckyEnvironment {
+    calls: AtomicUsize,
+}
+
+impl Environment for LuckyEnvironment {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        let fit: f64 = nn.genes().iter().sum();
+        if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            fit + 1000.0
+        } else {
+            fit
+        }
+    }
+}
+
+fn cosyne(config: Config) -> Cosyne {
+    let env = Box::new(LuckyEnvironment {
+        calls: AtomicUsize::new(0),
+    });
+    Cosyne::new(env, ANN::new(2, 1, Activation::Linear), config)
+}
+
+#[test]
+fn keep_best_champion_is_outlier() {
+    let mut config = Config::new(20);
+    config.seed = Some(5);
+    let mut cosyne = cosyne(config);
+    for _ in 0..50 {
+        cosyne.evolve().unwrap();
+    }
+    assert!(cosyne.champion().1 >= 1000.0);
+}
+
+#[test]
+fn reevaluated_champion() {
+    let mut config = Config::new(20);
+    config.seed = Some(5);
+    config.champion_policy = ChampionPolicy::Reevaluate;
+    let mut cosyne = cosyne(config);
+    cosyne.evolve().unwrap();
+    assert_eq!(cosyne.evaluations(), 20);
+    assert!(cosyne.champion().1 >= 1000.0);
+
+    for _ in 0..49 {
+        cosyne.evolve().unwrap();
+    }
+    // the champion is re-tested once per generation
+    assert_eq!(cosyne.evaluations(), 50 * 21 - 1);
+    assert!(cosyne.champion().1 < 100.0);
+}
+
+#[test]
+fn repeated_evaluations() {
+    let mut config = Config::new(20);
+    config.seed = Some(5);
+    config.evaluations_per_network = 3;
+    config.fitness_aggregation = FitnessAggregation::Median;
+    let mut cosyne = cosyne(config);
+    cosyne.evolve().unwrap();
+    assert_eq!(cosyne.evaluations(), 60);
+    // the median ignores the outlier
+    let champion = cosyne.champion();
+    assert_eq!(champion.1, champion.0.genes().iter().sum::<f64>());
+}
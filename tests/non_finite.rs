@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cosyne::{Activation, Config, Cosyne, CosyneError, Environment, NonFinitePolicy, ANN};
+
+/// Returns NaN for networks with a positive first weight
+struct NanEnvironment {}
+
+impl Environment for NanEnvironment {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        let genes = nn.genes();
+        if genes[0] > 0.0 {
+            f64::NAN
+        } else {
+            genes.iter().sum()
+        }
+    }
+}
+
+/// Returns NaN for the first nan_calls evaluations, no matter which network is evaluated
+struct FlakyEnvironment {
+    calls: AtomicUsize,
+    nan_calls: usize,
+}
+
+impl Environment for FlakyEnvironment {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        if self.calls.fetch_add(1, Ordering::SeqCst) < self.nan_calls {
+            f64::INFINITY
+        } else {
+            nn.genes().iter().sum()
+        }
+    }
+}
+
+fn cosyne(env: Box<dyn Environment>, policy: NonFinitePolicy) -> Cosyne {
+    let mut config = Config::new(20);
+    config.seed = Some(3);
+    config.non_finite_policy = policy;
+    Cosyne::new(env, ANN::new(2, 1, Activation::Linear), config)
+}
+
+#[test]
+fn treat_as_worst() {
+    let mut cosyne = cosyne(Box::new(NanEnvironment {}), NonFinitePolicy::TreatAsWorst);
+    for _ in 0..10 {
+        cosyne.evolve().unwrap();
+    }
+    let stats = &cosyne.history()[0];
+    assert!(stats.non_finite > 0);
+    assert_eq!(stats.evaluations, 20);
+    for stats in cosyne.history() {
+        assert!(stats.best.is_finite() && stats.worst.is_finite() && stats.mean.is_finite());
+    }
+    assert!(cosyne.champion().1.is_finite());
+    assert!(cosyne.champion().0.genes()[0] <= 0.0);
+}
+
+#[test]
+fn reevaluate() {
+    let env = FlakyEnvironment {
+        calls: AtomicUsize::new(0),
+        nan_calls: 20,
+    };
+    let mut cosyne = cosyne(Box::new(env), NonFinitePolicy::Reevaluate(20));
+    cosyne.evolve().unwrap();
+    let stats = &cosyne.history()[0];
+    assert_eq!(stats.non_finite, 20);
+    assert_eq!(stats.evaluations, 40);
+    assert!(stats.worst.is_finite());
+}
+
+#[test]
+fn all_non_finite_generation() {
+    let env = FlakyEnvironment {
+        calls: AtomicUsize::new(0),
+        nan_calls: 20,
+    };
+    let mut cosyne = cosyne(Box::new(env), NonFinitePolicy::TreatAsWorst);
+    cosyne.evolve().unwrap();
+    // no network of the first generation has a fitness to become the champion with
+    assert_eq!(cosyne.champion().1, f64::MIN);
+    let stats = &cosyne.history()[0];
+    for v in [
+        stats.best,
+        stats.mean,
+        stats.median,
+        stats.worst,
+        stats.std_dev,
+    ] {
+        assert!(v.is_finite());
+    }
+    cosyne.evolve().unwrap();
+    assert_eq!(cosyne.champion().1, cosyne.history()[1].best);
+}
+
+#[test]
+fn abort() {
+    let mut cosyne = cosyne(Box::new(NanEnvironment {}), NonFinitePolicy::Abort);
+    let err = cosyne.evolve().unwrap_err();
+    assert!(matches!(
+        err,
+        CosyneError::NonFiniteFitness { fitness, .. } if fitness.is_nan()
+    ));
+    assert_eq!(cosyne.generation(), 0);
+    assert_eq!(cosyne.evaluations(), 0);
+    assert!(cosyne.history().is_empty());
+}
@@ -0,0 +1,70 @@
+use cosyne::{
+    Activation, BehaviorEnvironment, Config, Cosyne, CosyneError, Environment, Selection, ANN,
+};
+
+/// The behavior is the output of the network, the fitness rewards outputs close to 0
+struct Output {}
+
+impl BehaviorEnvironment for Output {
+    fn evaluate(&self, nn: &mut ANN) -> (f64, Vec<f64>) {
+        let y = nn.forward(vec![1.0])[0];
+        (-y.abs(), vec![y])
+    }
+}
+
+impl Environment for Output {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        -nn.forward(vec![1.0])[0].abs()
+    }
+}
+
+/// Mean distance of the last archived behaviors to the fittest output 0
+fn recent_distance(cosyne: &Cosyne) -> f64 {
+    let archive = cosyne.novelty_archive();
+    let recent = &archive[archive.len() - 10..];
+    recent.iter().map(|b| b[0].abs()).sum::<f64>() / recent.len() as f64
+}
+
+fn cosyne(weight: f64) -> Cosyne {
+    let config = Config::builder()
+        .pop_size(20)
+        .selection(Selection::Novelty(weight))
+        .novelty_neighbors(5)
+        .seed(3)
+        .build()
+        .unwrap();
+    Cosyne::new_with_behavior(
+        Box::new(Output {}),
+        ANN::new(1, 1, Activation::Linear),
+        config,
+    )
+}
+
+#[test]
+fn novelty_search_explores() {
+    let mut novelty = cosyne(1.0);
+    let mut fitness = cosyne(0.0);
+    for _ in 0..60 {
+        novelty.evolve().unwrap();
+        fitness.evolve().unwrap();
+    }
+
+    assert_eq!(novelty.novelty_archive().len(), 60);
+    assert!(novelty.novelty_archive().iter().all(|b| b.len() == 1));
+    // fitness converges to outputs close to 0, novelty keeps exploring other outputs
+    assert!(recent_distance(&novelty) > 2.0 * recent_distance(&fitness));
+}
+
+#[test]
+fn novelty_requires_behavior() {
+    let config = Config::builder()
+        .pop_size(20)
+        .selection(Selection::Novelty(0.5))
+        .build()
+        .unwrap();
+    let nn = ANN::new(1, 1, Activation::Linear);
+    assert_eq!(
+        Cosyne::try_new(Box::new(Output {}), nn, config).err(),
+        Some(CosyneError::MissingBehavior)
+    );
+}
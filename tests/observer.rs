@@ -0,0 +1,106 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosyne::{
+    Activation, Config, Cosyne, CosyneObserver, Environment, GenerationStats, StopCondition,
+    StopReason, ANN,
+};
+
+/// Fitness grows with the sum of all weights, so it keeps improving
+struct SumEnvironment {}
+
+impl Environment for SumEnvironment {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        nn.genes().iter().sum()
+    }
+}
+
+#[derive(Default)]
+struct Log {
+    starts: Vec<usize>,
+    evaluated: Vec<(usize, f64)>,
+    champions: Vec<f64>,
+    ends: Vec<GenerationStats>,
+}
+
+/// Records every notification and stops once the best fitness of a generation is high enough
+struct Recorder {
+    log: Rc<RefCell<Log>>,
+    target: f64,
+}
+
+impl CosyneObserver for Recorder {
+    fn on_generation_start(&mut self, generation: usize) {
+        self.log.borrow_mut().starts.push(generation);
+    }
+
+    fn on_evaluated(&mut self, j: usize, fitness: f64) {
+        self.log.borrow_mut().evaluated.push((j, fitness));
+    }
+
+    fn on_new_champion(&mut self, champion: &ANN, fitness: f64) {
+        assert_eq!(champion.genes().iter().sum::<f64>(), fitness);
+        self.log.borrow_mut().champions.push(fitness);
+    }
+
+    fn on_generation_end(&mut self, stats: &GenerationStats) {
+        self.log.borrow_mut().ends.push(stats.clone());
+    }
+
+    fn should_stop(&self) -> bool {
+        self.log
+            .borrow()
+            .ends
+            .last()
+            .is_some_and(|s| s.best >= self.target)
+    }
+}
+
+fn cosyne() -> Cosyne {
+    let mut config = Config::new(20);
+    config.seed = Some(0);
+    Cosyne::new(
+        Box::new(SumEnvironment {}),
+        ANN::new(2, 1, Activation::Linear),
+        config,
+    )
+}
+
+#[test]
+fn observer_hooks() {
+    let log = Rc::new(RefCell::new(Log::default()));
+    let mut cosyne = cosyne();
+    cosyne.add_observer(Box::new(Recorder {
+        log: log.clone(),
+        target: f64::INFINITY,
+    }));
+    let report = cosyne.run(StopCondition::MaxGenerations(5)).unwrap();
+    assert_eq!(report.reasons, vec![StopReason::MaxGenerations]);
+
+    let log = log.borrow();
+    assert_eq!(log.starts, vec![0, 1, 2, 3, 4]);
+    assert_eq!(log.evaluated.len(), 5 * 20);
+    // networks are reported in index order within each generation
+    for (k, (j, _)) in log.evaluated.iter().enumerate() {
+        assert_eq!(*j, k % 20);
+    }
+    // every new champion improves on the last one and the final one is the reported champion
+    assert!(log.champions.windows(2).all(|w| w[1] > w[0]));
+    assert_eq!(*log.champions.last().unwrap(), cosyne.champion().1);
+    let generations: Vec<usize> = log.ends.iter().map(|s| s.generation).collect();
+    assert_eq!(generations, log.starts);
+    assert_eq!(log.ends.last().unwrap().best, cosyne.history()[4].best);
+}
+
+#[test]
+fn observer_early_stopping() {
+    let log = Rc::new(RefCell::new(Log::default()));
+    let mut cosyne = cosyne();
+    cosyne.add_observer(Box::new(Recorder {
+        log: log.clone(),
+        target: 3.0,
+    }));
+    let report = cosyne.run(StopCondition::MaxGenerations(1000)).unwrap();
+    assert_eq!(report.reasons, vec![StopReason::Observer]);
+    assert!(report.generations < 1000);
+    assert!(cosyne.history().last().unwrap().best >= 3.0);
+}
@@ -0,0 +1,66 @@
+use cosyne::benchmarks::Xor;
+use cosyne::{Activation, Config, Cosyne, Environment, ANN};
+
+#[test]
+fn same_seed_same_run() {
+    let (genes_a, fit_history_a) = run(42);
+    let (genes_b, fit_history_b) = run(42);
+    assert_eq!(genes_a, genes_b);
+    assert_eq!(fit_history_a, fit_history_b);
+
+    let (genes_c, _) = run(43);
+    assert_ne!(genes_a, genes_c);
+}
+
+fn run(seed: u64) -> (Vec<f64>, Vec<f64>) {
+    let mut config = Config::new(50);
+    config.seed = Some(seed);
+    let env = Box::new(Xor::new());
+    let mut nn = ANN::new(2, 1, Activation::Relu);
+    nn.add_layer(2, Activation::Relu);
+    let mut cosyne = Cosyne::new(env, nn, config);
+    for _ in 0..20 {
+        cosyne.evolve().unwrap();
+    }
+    (
+        cosyne.champion().0.genes(),
+        cosyne.champion_fit_history().to_vec(),
+    )
+}
+
+/// The recorded champion history is checked both with and without the parallel feature,
+/// so concurrent evaluation has to reproduce the sequential run exactly
+#[test]
+fn parallel_matches_sequential() {
+    let mut config = Config::new(20);
+    config.seed = Some(5);
+    let nn = ANN::new(3, 1, Activation::Linear);
+    let mut cosyne = Cosyne::new(Box::new(Sphere {}), nn, config);
+    for _ in 0..10 {
+        cosyne.evolve().unwrap();
+    }
+    assert_eq!(
+        cosyne.champion_fit_history(),
+        &[
+            -0.30155982835510725,
+            -0.18262001394903069,
+            -0.18262001394903069,
+            -0.13730359160653666,
+            -0.13730359160653666,
+            -0.0694357059551758,
+            -0.04832863874221616,
+            -0.04832863874221616,
+            -0.030604135477935,
+            -0.030604135477935,
+        ]
+    );
+}
+
+/// Fitness improves steadily as the weights approach 0.5
+struct Sphere {}
+
+impl Environment for Sphere {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        -nn.genes().iter().map(|w| (w - 0.5).powi(2)).sum::<f64>()
+    }
+}
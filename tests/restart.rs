@@ -0,0 +1,77 @@
+use cosyne::{Activation, Config, Cosyne, Environment, RestartStrategy, ANN};
+
+struct Constant {}
+
+impl Environment for Constant {
+    fn evaluate(&self, _nn: &mut ANN) -> f64 {
+        1.0
+    }
+}
+
+/// Deterministic environment rewarding large weights
+struct SumOfWeights {}
+
+impl Environment for SumOfWeights {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        nn.genes().iter().sum()
+    }
+}
+
+#[test]
+fn restart_after_stagnation() {
+    let config = Config::builder()
+        .pop_size(10)
+        .restart(RestartStrategy {
+            stagnation: Some(3),
+            double_pop_size: true,
+            ..Default::default()
+        })
+        .seed(0)
+        .build()
+        .unwrap();
+    let mut cosyne = Cosyne::new(
+        Box::new(Constant {}),
+        ANN::new(2, 1, Activation::Linear),
+        config,
+    );
+    for _ in 0..12 {
+        cosyne.evolve().unwrap();
+    }
+
+    // every population gets 4 generations to improve, doubling its size at each restart
+    assert_eq!(cosyne.restart_generations(), &[4, 8, 12]);
+    assert_eq!(cosyne.evaluations(), 4 * 10 + 4 * 20 + 4 * 40);
+    assert_eq!(cosyne.history().len(), 12);
+}
+
+#[test]
+fn keep_champion_across_restarts() {
+    let config = Config::builder()
+        .pop_size(10)
+        .restart(RestartStrategy {
+            // restart after every generation
+            min_diversity: Some(f64::MAX),
+            keep_champion: true,
+            ..Default::default()
+        })
+        .seed(0)
+        .build()
+        .unwrap();
+    let mut cosyne = Cosyne::new(
+        Box::new(SumOfWeights {}),
+        ANN::new(2, 1, Activation::Linear),
+        config,
+    );
+    for _ in 0..5 {
+        cosyne.evolve().unwrap();
+    }
+
+    assert_eq!(cosyne.restart_generations(), &[1, 2, 3, 4, 5]);
+    let history = cosyne.history();
+    for g in 1..5 {
+        // the champion is evaluated unchanged in the new population
+        assert!(history[g].best >= history[g - 1].champion);
+    }
+    let h = cosyne.champion_fit_history();
+    assert!(h.windows(2).all(|w| w[1] >= w[0]));
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use cosyne::{
+    Activation, Config, Cosyne, CosyneError, Environment, StopCondition, StopReason, ANN,
+};
+
+/// Fitness grows with the sum of all weights, so it keeps improving
+struct SumEnvironment {}
+
+impl Environment for SumEnvironment {
+    fn evaluate(&self, nn: &mut ANN) -> f64 {
+        nn.genes().iter().sum()
+    }
+}
+
+/// Every network is equally fit, so the champion never improves
+struct ConstEnvironment {}
+
+impl Environment for ConstEnvironment {
+    fn evaluate(&self, _nn: &mut ANN) -> f64 {
+        1.0
+    }
+}
+
+fn cosyne(env: Box<dyn Environment>) -> Cosyne {
+    let mut config = Config::new(20);
+    config.seed = Some(0);
+    Cosyne::new(env, ANN::new(2, 1, Activation::Linear), config)
+}
+
+#[test]
+fn run_max_generations() {
+    let mut cosyne = cosyne(Box::new(SumEnvironment {}));
+    let report = cosyne.run(StopCondition::MaxGenerations(10)).unwrap();
+    assert_eq!(report.reasons, vec![StopReason::MaxGenerations]);
+    assert_eq!(report.generations, 10);
+    assert_eq!(report.evaluations, 200);
+    assert_eq!(report.champion.1, cosyne.champion().1);
+
+    // generations are counted from the start of the optimization
+    let report = cosyne.run(StopCondition::MaxGenerations(15)).unwrap();
+    assert_eq!(report.generations, 5);
+}
+
+#[test]
+fn run_max_evaluations() {
+    let mut cosyne = cosyne(Box::new(SumEnvironment {}));
+    let report = cosyne.run(StopCondition::MaxEvaluations(50)).unwrap();
+    assert_eq!(report.reasons, vec![StopReason::MaxEvaluations]);
+    assert_eq!(report.evaluations, 60);
+}
+
+#[test]
+fn run_target_fitness() {
+    let mut cosyne = cosyne(Box::new(SumEnvironment {}));
+    let report = cosyne
+        .run(StopCondition::Any(vec![
+            StopCondition::TargetFitness(2.0),
+            StopCondition::MaxGenerations(1000),
+        ]))
+        .unwrap();
+    assert_eq!(report.reasons, vec![StopReason::TargetFitness]);
+    assert!(report.champion.1 >= 2.0);
+}
+
+#[test]
+fn run_stagnation() {
+    let mut cosyne = cosyne(Box::new(ConstEnvironment {}));
+    let report = cosyne.run(StopCondition::Stagnation(5)).unwrap();
+    assert_eq!(report.reasons, vec![StopReason::Stagnation]);
+    assert_eq!(report.generations, 6);
+}
+
+#[test]
+fn run_all() {
+    let mut cosyne = cosyne(Box::new(ConstEnvironment {}));
+    let report = cosyne
+        .run(StopCondition::All(vec![
+            StopCondition::Stagnation(2),
+            StopCondition::MaxGenerations(8),
+            StopCondition::WallClock(Duration::ZERO),
+        ]))
+        .unwrap();
+    assert_eq!(
+        report.reasons,
+        vec![
+            StopReason::Stagnation,
+            StopReason::MaxGenerations,
+            StopReason::WallClock
+        ]
+    );
+    assert_eq!(report.generations, 8);
+}
+
+#[test]
+fn run_invalid_stop_condition() {
+    let mut cosyne = cosyne(Box::new(ConstEnvironment {}));
+    let nested = StopCondition::Any(vec![
+        StopCondition::MaxGenerations(3),
+        StopCondition::All(vec![]),
+    ]);
+    for stop in [StopCondition::Any(vec![]), nested] {
+        assert_eq!(
+            cosyne.run(stop).unwrap_err(),
+            CosyneError::EmptyStopCondition
+        );
+    }
+    assert_eq!(
+        cosyne.run(StopCondition::Stagnation(0)).unwrap_err(),
+        CosyneError::MustBePositive {
+            name: "stagnation generations"
+        }
+    );
+    assert_eq!(cosyne.generation(), 0);
+}
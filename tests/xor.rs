@@ -1,4 +1,4 @@
-use cosyne::{Activation, Config, Cosyne, Environment, ANN};
+use cosyne::{Activation, Config, Cosyne, Environment, StopCondition, ANN};
 
 #[test]
 fn xor() {
@@ -8,10 +8,8 @@ fn xor() {
     let mut nn = ANN::new(2, 1, activation);
     nn.add_layer(2, Activation::Relu);
     let mut cosyne = Cosyne::new(env, nn, config);
-    for _ in 0..100 {
-        cosyne.evolve();
-    }
-    let champion = cosyne.champion();
+    let report = cosyne.run(StopCondition::MaxGenerations(100)).unwrap();
+    let champion = &report.champion;
     println!("champion: {:?}", champion);
     assert!(champion.1 > 3.9);
 }